@@ -0,0 +1,174 @@
+//! # Derive macros
+//!
+//! Procedural macros for `bacy`. Use them through `bacy` with the `derive`
+//! feature enabled rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input};
+
+/// Derives `TableDecrypt` for a FlatBuffers object API type.
+///
+/// On a struct with named fields every field is decrypted with the key
+/// passed to `decrypt`, and `TableRecord` is implemented with the struct
+/// name minus a trailing `T` as key name. Override it with
+/// `#[table(key = "Name")]` and exclude fields with `#[table(skip)]`.
+///
+/// A single-field tuple struct (flatc enum) marked with `#[table(enum)]` is
+/// decrypted as an enum via `decrypt_enum`. Other shapes, and `key` on an
+/// enum, are rejected.
+#[proc_macro_derive(TableDecrypt, attributes(table))]
+pub fn derive_table_decrypt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, Mode::Decrypt).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `TableEncrypt`, the inverse of `TableDecrypt`.
+///
+/// Accepts the same shapes and `table` attributes. The key name is
+/// provided by the `TableRecord` impl generated by `TableDecrypt`.
+#[proc_macro_derive(TableEncrypt, attributes(table))]
+pub fn derive_table_encrypt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, Mode::Encrypt).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Decrypt,
+    Encrypt
+}
+
+fn expand(input: DeriveInput, mode: Mode) -> syn::Result<TokenStream2> {
+    let (trait_name, method, enum_fn, enum_try) = match mode {
+        Mode::Decrypt => (quote!(TableDecrypt), quote!(decrypt), quote!(decrypt_enum), quote!()),
+        Mode::Encrypt => (quote!(TableEncrypt), quote!(encrypt), quote!(encrypt_enum), quote!(?))
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "table derives can only be used on structs"));
+    };
+    let attrs = parse_struct_attrs(&input.attrs)?;
+
+    match &data.fields {
+        Fields::Named(_) if attrs.is_enum => Err(syn::Error::new(
+            data.fields.span(),
+            "`#[table(enum)]` requires a single-field tuple struct"
+        )),
+        Fields::Named(fields) => {
+            let mut statements = Vec::new();
+            for field in &fields.named {
+                if parse_field_skip(&field.attrs)? {
+                    continue;
+                }
+                let ident = &field.ident;
+                statements.push(quote! {
+                    ::bacy::crypto::table::#trait_name::#method(&mut self.#ident, key)?;
+                });
+            }
+
+            let key_name = match attrs.key {
+                Some(key) => key.value(),
+                None => default_key_name(name)
+            };
+
+            let record = (mode == Mode::Decrypt).then(|| {
+                quote! {
+                    impl #impl_generics ::bacy::crypto::table::TableRecord for #name #ty_generics #where_clause {
+                        const KEY_NAME: &'static str = #key_name;
+                    }
+                }
+            });
+
+            Ok(quote! {
+                impl #impl_generics ::bacy::crypto::table::#trait_name for #name #ty_generics #where_clause {
+                    fn #method(
+                        &mut self,
+                        key: &[u8]
+                    ) -> ::core::result::Result<(), ::bacy::error::TableEncryptionError> {
+                        #(#statements)*
+                        Ok(())
+                    }
+                }
+
+                #record
+            })
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 && attrs.is_enum => {
+            if let Some(key) = attrs.key {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "`#[table(key)]` is only supported on structs with named fields"
+                ));
+            }
+            Ok(quote! {
+                impl #impl_generics ::bacy::crypto::table::#trait_name for #name #ty_generics #where_clause {
+                    fn #method(
+                        &mut self,
+                        key: &[u8]
+                    ) -> ::core::result::Result<(), ::bacy::error::TableEncryptionError> {
+                        *self = ::bacy::crypto::table::#enum_fn(*self, key)#enum_try;
+                        Ok(())
+                    }
+                }
+            })
+        }
+        _ => Err(syn::Error::new(
+            data.fields.span(),
+            "table derives require named fields or a single-field tuple struct marked \
+             `#[table(enum)]`"
+        ))
+    }
+}
+
+fn default_key_name(name: &syn::Ident) -> String {
+    let name = name.to_string();
+    match name.strip_suffix('T') {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => name
+    }
+}
+
+#[derive(Default)]
+struct StructAttrs {
+    key: Option<LitStr>,
+    is_enum: bool
+}
+
+fn parse_struct_attrs(attrs: &[syn::Attribute]) -> syn::Result<StructAttrs> {
+    let mut parsed = StructAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                parsed.key = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("enum") {
+                parsed.is_enum = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute"))
+            }
+        })?;
+    }
+    Ok(parsed)
+}
+
+fn parse_field_skip(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
@@ -31,28 +31,46 @@ pub enum BacyErrorCode {
     Base64Decode = 4,
     FromUtf16 = 5,
     StringConversion = 6,
+    Overflow = 7,
+    OutOfRange = 8,
+    Cancelled = 9,
+    EnumCollision = 10,
+    DuplicatePath = 11,
+    UnsupportedCrc = 12,
+    ChecksumMismatch = 13,
+    SourceInBatch = 14,
     PanicUnwind = -1,
     NullPointer = -2
 }
 
-impl From<&bacy::error::HashError> for BacyErrorCode {
-    fn from(e: &bacy::error::HashError) -> Self {
+impl From<&bacy_core::error::HashError> for BacyErrorCode {
+    fn from(e: &bacy_core::error::HashError) -> Self {
         match e {
-            bacy::error::HashError::Io(_) => BacyErrorCode::Io,
-            bacy::error::HashError::InvalidPath => BacyErrorCode::InvalidPath,
-            bacy::error::HashError::Mismatch { .. } => BacyErrorCode::Mismatch
+            bacy_core::error::HashError::Io(_) => BacyErrorCode::Io,
+            bacy_core::error::HashError::InvalidPath => BacyErrorCode::InvalidPath,
+            bacy_core::error::HashError::Mismatch { .. } => BacyErrorCode::Mismatch,
+            bacy_core::error::HashError::ChecksumMismatch { .. } => BacyErrorCode::ChecksumMismatch,
+            bacy_core::error::HashError::Cancelled => BacyErrorCode::Cancelled,
+            bacy_core::error::HashError::DuplicatePath(_) => BacyErrorCode::DuplicatePath,
+            bacy_core::error::HashError::SourceInBatch(_) => BacyErrorCode::SourceInBatch,
+            bacy_core::error::HashError::Spec(bacy_core::error::CrcSpecError::OutOfRange {
+                ..
+            }) => BacyErrorCode::OutOfRange,
+            bacy_core::error::HashError::Spec(_) => BacyErrorCode::UnsupportedCrc
         }
     }
 }
 
-impl From<&bacy::error::TableEncryptionError> for BacyErrorCode {
-    fn from(e: &bacy::error::TableEncryptionError) -> Self {
+impl From<&bacy_core::error::TableEncryptionError> for BacyErrorCode {
+    fn from(e: &bacy_core::error::TableEncryptionError) -> Self {
         match e {
-            bacy::error::TableEncryptionError::Base64Decode(_) => BacyErrorCode::Base64Decode,
-            bacy::error::TableEncryptionError::FromUtf16Error(_) => BacyErrorCode::FromUtf16,
-            bacy::error::TableEncryptionError::StringConversionFailed => {
+            bacy_core::error::TableEncryptionError::Base64Decode(_) => BacyErrorCode::Base64Decode,
+            bacy_core::error::TableEncryptionError::FromUtf16Error(_) => BacyErrorCode::FromUtf16,
+            bacy_core::error::TableEncryptionError::StringConversionFailed => {
                 BacyErrorCode::StringConversion
             }
+            bacy_core::error::TableEncryptionError::Overflow => BacyErrorCode::Overflow,
+            bacy_core::error::TableEncryptionError::EnumCollision(_) => BacyErrorCode::EnumCollision
         }
     }
 }
@@ -123,7 +141,7 @@ pub unsafe extern "C" fn bacy_crc_compute_streaming(
         return BacyErrorCode::NullPointer as i32;
     };
     let result = catch_unwind(|| {
-        bacy::hash::crc::compute_streaming(Path::new(path), buffer_size as usize, None)
+        bacy_core::hash::crc::compute_streaming(Path::new(path), buffer_size as usize, None)
     });
     match result {
         Ok(Ok(value)) => {
@@ -141,7 +159,7 @@ pub unsafe extern "C" fn bacy_crc_compute_bytes(data: *const u8, len: usize) ->
         return 0;
     }
     let slice = std::slice::from_raw_parts(data, len);
-    catch_unwind(|| bacy::hash::crc::compute_bytes(slice, None)).unwrap_or(0)
+    catch_unwind(|| bacy_core::hash::crc::compute_bytes(slice, None)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -153,14 +171,14 @@ pub unsafe extern "C" fn bacy_crc_compare(
     let Some(path) = str_from_ptr(path) else {
         return BacyErrorCode::NullPointer as i32;
     };
-    let result = catch_unwind(|| bacy::hash::crc::compare(Path::new(path), expected_crc));
+    let result = catch_unwind(|| bacy_core::hash::crc::compare(Path::new(path), expected_crc));
     match result {
         Ok(Ok(())) => BacyErrorCode::Success as i32,
         Ok(Err(e)) => {
-            if let bacy::error::HashError::Mismatch { expected, actual } = e {
-                if !out_mismatch.is_null() {
-                    *out_mismatch = BacyCrcMismatch { expected, actual };
-                }
+            if let bacy_core::error::HashError::Mismatch { expected, actual } = e
+                && !out_mismatch.is_null()
+            {
+                *out_mismatch = BacyCrcMismatch { expected, actual };
             }
             BacyErrorCode::from(&e) as i32
         }
@@ -179,7 +197,7 @@ pub unsafe extern "C" fn bacy_crc_forge(file_path: *const c_char, target_crc: u3
         return BacyErrorCode::NullPointer as i32;
     };
     let result = catch_unwind(|| {
-        let manipulator = bacy::utils::crc_manipulator::CrcManipulator::new(file_path);
+        let manipulator = bacy_core::utils::crc_manipulator::CrcManipulator::new(file_path);
         manipulator.forge_crc(target_crc)
     });
     match result {
@@ -189,6 +207,32 @@ pub unsafe extern "C" fn bacy_crc_forge(file_path: *const c_char, target_crc: u3
     }
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_crc_forge_at(
+    file_path: *const c_char,
+    target_crc: u32,
+    offset: u64,
+    insert: bool
+) -> i32 {
+    let Some(file_path) = str_from_ptr(file_path) else {
+        return BacyErrorCode::NullPointer as i32;
+    };
+    let mode = if insert {
+        bacy_core::utils::crc_manipulator::PatchMode::Insert
+    } else {
+        bacy_core::utils::crc_manipulator::PatchMode::Overwrite
+    };
+    let result = catch_unwind(|| {
+        let manipulator = bacy_core::utils::crc_manipulator::CrcManipulator::new(file_path);
+        manipulator.forge_crc_at(target_crc, offset, mode)
+    });
+    match result {
+        Ok(Ok(())) => BacyErrorCode::Success as i32,
+        Ok(Err(e)) => BacyErrorCode::from(&e) as i32,
+        Err(_) => BacyErrorCode::PanicUnwind as i32
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_crc_match_file(
     file_path: *const c_char,
@@ -200,7 +244,7 @@ pub unsafe extern "C" fn bacy_crc_match_file(
         return BacyErrorCode::NullPointer as i32;
     };
     let result = catch_unwind(|| {
-        let manipulator = bacy::utils::crc_manipulator::CrcManipulator::new(file_path);
+        let manipulator = bacy_core::utils::crc_manipulator::CrcManipulator::new(file_path);
         manipulator.match_file(Path::new(target_file_path))
     });
     match result {
@@ -216,7 +260,7 @@ pub unsafe extern "C" fn bacy_md5_to_hex_string(data: *const u8, len: usize) ->
         return ptr::null_mut();
     }
     let slice = std::slice::from_raw_parts(data, len);
-    match catch_unwind(|| bacy::crypto::md5::to_hex_string(slice)) {
+    match catch_unwind(|| bacy_core::crypto::md5::to_hex_string(slice)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -228,7 +272,7 @@ pub unsafe extern "C" fn bacy_md5_compute_hash(data: *const u8, len: usize) -> B
         return BacyBytes::null();
     }
     let slice = std::slice::from_raw_parts(data, len);
-    match catch_unwind(|| bacy::crypto::md5::compute_hash(slice).to_vec()) {
+    match catch_unwind(|| bacy_core::crypto::md5::compute_hash(slice).to_vec()) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -246,7 +290,7 @@ pub unsafe extern "C" fn bacy_md5_compute_hash_hmac(
     }
     let data = std::slice::from_raw_parts(data, data_len);
     let key = std::slice::from_raw_parts(key, key_len);
-    match catch_unwind(|| bacy::crypto::md5::compute_hash_hmac(data, key).to_vec()) {
+    match catch_unwind(|| bacy_core::crypto::md5::compute_hash_hmac(data, key).to_vec()) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -255,7 +299,7 @@ pub unsafe extern "C" fn bacy_md5_compute_hash_hmac(
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_md5_compute_hash_str(source: *const c_char) -> *mut c_char {
     let Some(source) = str_from_ptr(source) else { return ptr::null_mut() };
-    match catch_unwind(|| bacy::crypto::md5::compute_hash_str(source)) {
+    match catch_unwind(|| bacy_core::crypto::md5::compute_hash_str(source)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -269,7 +313,7 @@ pub unsafe extern "C" fn bacy_md5_compute_hash_str_hmac(
     let (Some(source), Some(key)) = (str_from_ptr(source), str_from_ptr(key)) else {
         return ptr::null_mut();
     };
-    match catch_unwind(|| bacy::crypto::md5::compute_hash_str_hmac(source, key)) {
+    match catch_unwind(|| bacy_core::crypto::md5::compute_hash_str_hmac(source, key)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -278,7 +322,7 @@ pub unsafe extern "C" fn bacy_md5_compute_hash_str_hmac(
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_md5_compute_digest(source: *const c_char) -> u32 {
     let Some(source) = str_from_ptr(source) else { return 0 };
-    catch_unwind(|| bacy::crypto::md5::compute_digest(source)).unwrap_or(0)
+    catch_unwind(|| bacy_core::crypto::md5::compute_digest(source)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -289,13 +333,13 @@ pub unsafe extern "C" fn bacy_md5_compute_digest_hmac(
     let (Some(source), Some(key)) = (str_from_ptr(source), str_from_ptr(key)) else {
         return 0;
     };
-    catch_unwind(|| bacy::crypto::md5::compute_digest_hmac(source, key)).unwrap_or(0)
+    catch_unwind(|| bacy_core::crypto::md5::compute_digest_hmac(source, key)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_md5_compute_digest64(source: *const c_char) -> u64 {
     let Some(source) = str_from_ptr(source) else { return 0 };
-    catch_unwind(|| bacy::crypto::md5::compute_digest64(source)).unwrap_or(0)
+    catch_unwind(|| bacy_core::crypto::md5::compute_digest64(source)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -306,13 +350,13 @@ pub unsafe extern "C" fn bacy_md5_compute_digest64_hmac(
     let (Some(source), Some(key)) = (str_from_ptr(source), str_from_ptr(key)) else {
         return 0;
     };
-    catch_unwind(|| bacy::crypto::md5::compute_digest64_hmac(source, key)).unwrap_or(0)
+    catch_unwind(|| bacy_core::crypto::md5::compute_digest64_hmac(source, key)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_md5_compute_head(source: *const c_char) -> *mut c_char {
     let Some(source) = str_from_ptr(source) else { return ptr::null_mut() };
-    match catch_unwind(|| bacy::crypto::md5::compute_head(source)) {
+    match catch_unwind(|| bacy_core::crypto::md5::compute_head(source)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -324,7 +368,7 @@ pub unsafe extern "C" fn bacy_sha_compute(data: *const u8, len: usize) -> BacyBy
         return BacyBytes::null();
     }
     let slice = std::slice::from_raw_parts(data, len);
-    match catch_unwind(|| bacy::hash::sha::compute(slice).to_vec()) {
+    match catch_unwind(|| bacy_core::hash::sha::compute(slice).to_vec()) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -333,7 +377,7 @@ pub unsafe extern "C" fn bacy_sha_compute(data: *const u8, len: usize) -> BacyBy
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_sha_compute_str(source: *const c_char) -> BacyBytes {
     let Some(source) = str_from_ptr(source) else { return BacyBytes::null() };
-    match catch_unwind(|| bacy::hash::sha::compute_str(source).to_vec()) {
+    match catch_unwind(|| bacy_core::hash::sha::compute_str(source).to_vec()) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -345,13 +389,13 @@ pub unsafe extern "C" fn bacy_xxhash_calculate_hash(data: *const u8, len: usize)
         return 0;
     }
     let slice = std::slice::from_raw_parts(data, len);
-    catch_unwind(|| bacy::hash::xxhash::calculate_hash(slice)).unwrap_or(0)
+    catch_unwind(|| bacy_core::hash::xxhash::calculate_hash(slice)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_xxhash_calculate_hash_str(s: *const c_char) -> u32 {
     let Some(s) = str_from_ptr(s) else { return 0 };
-    catch_unwind(|| bacy::hash::xxhash::calculate_hash_str(s)).unwrap_or(0)
+    catch_unwind(|| bacy_core::hash::xxhash::calculate_hash_str(s)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -360,13 +404,13 @@ pub unsafe extern "C" fn bacy_xxhash_calculate_hash64(data: *const u8, len: usiz
         return 0;
     }
     let slice = std::slice::from_raw_parts(data, len);
-    catch_unwind(|| bacy::hash::xxhash::calculate_hash64(slice)).unwrap_or(0)
+    catch_unwind(|| bacy_core::hash::xxhash::calculate_hash64(slice)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_xxhash_calculate_hash64_str(s: *const c_char) -> u64 {
     let Some(s) = str_from_ptr(s) else { return 0 };
-    catch_unwind(|| bacy::hash::xxhash::calculate_hash64_str(s)).unwrap_or(0)
+    catch_unwind(|| bacy_core::hash::xxhash::calculate_hash64_str(s)).unwrap_or(0)
 }
 
 #[unsafe(no_mangle)]
@@ -381,7 +425,7 @@ pub unsafe extern "C" fn bacy_xor_encrypt(
     }
     let slice = std::slice::from_raw_parts_mut(data, len);
     let result = catch_unwind(std::panic::AssertUnwindSafe(|| {
-        bacy::crypto::xor::encrypt(slice, offset as usize, length as usize);
+        bacy_core::crypto::xor::encrypt(slice, offset as usize, length as usize);
     }));
     match result {
         Ok(()) => BacyErrorCode::Success as i32,
@@ -401,7 +445,7 @@ pub unsafe extern "C" fn bacy_xor_encrypt_with_key(
     }
     let data = std::slice::from_raw_parts(data, data_len);
     let key = std::slice::from_raw_parts(key, key_len);
-    match catch_unwind(|| bacy::crypto::xor::encrypt_with_key(data, key)) {
+    match catch_unwind(|| bacy_core::crypto::xor::encrypt_with_key(data, key)) {
         Ok(Some(v)) => BacyBytes::from_vec(v),
         Ok(None) | Err(_) => BacyBytes::null()
     }
@@ -419,7 +463,7 @@ pub unsafe extern "C" fn bacy_xor_exact(
     }
     let value = std::slice::from_raw_parts(value, value_len);
     let key = std::slice::from_raw_parts(key, key_len);
-    match catch_unwind(|| bacy::crypto::xor::xor_exact(value, key)) {
+    match catch_unwind(|| bacy_core::crypto::xor::xor_exact(value, key)) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -437,8 +481,9 @@ pub unsafe extern "C" fn bacy_xor_inplace_bytes(
     }
     let slice = std::slice::from_raw_parts_mut(data, len);
     let key = std::slice::from_raw_parts(key, key_len);
-    let result =
-        catch_unwind(std::panic::AssertUnwindSafe(|| bacy::crypto::xor::xor_inplace(slice, key)));
+    let result = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        bacy_core::crypto::xor::xor_inplace(slice, key)
+    }));
     match result {
         Ok(()) => BacyErrorCode::Success as i32,
         Err(_) => BacyErrorCode::PanicUnwind as i32
@@ -448,7 +493,7 @@ pub unsafe extern "C" fn bacy_xor_inplace_bytes(
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_table_create_key(name: *const c_char) -> BacyBytes {
     let Some(name) = str_from_ptr(name) else { return BacyBytes::null() };
-    match catch_unwind(|| bacy::crypto::table::create_key(name).to_vec()) {
+    match catch_unwind(|| bacy_core::crypto::table::create_key(name).to_vec()) {
         Ok(v) => BacyBytes::from_vec(v),
         Err(_) => BacyBytes::null()
     }
@@ -460,7 +505,7 @@ pub unsafe extern "C" fn bacy_table_create_password(
     length: u64
 ) -> *mut c_char {
     let Some(key) = str_from_ptr(key) else { return ptr::null_mut() };
-    match catch_unwind(|| bacy::crypto::table::create_password(key, length as usize)) {
+    match catch_unwind(|| bacy_core::crypto::table::create_password(key, length as usize)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -476,7 +521,7 @@ pub unsafe extern "C" fn bacy_table_xor(name: *const c_char, data: *mut u8, len:
     }
     let slice = std::slice::from_raw_parts_mut(data, len);
     let result =
-        catch_unwind(std::panic::AssertUnwindSafe(|| bacy::crypto::table::xor(name, slice)));
+        catch_unwind(std::panic::AssertUnwindSafe(|| bacy_core::crypto::table::xor(name, slice)));
     match result {
         Ok(()) => BacyErrorCode::Success as i32,
         Err(_) => BacyErrorCode::PanicUnwind as i32
@@ -489,7 +534,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_i32(value: i32, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_i32(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_i32(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -498,7 +543,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_i64(value: i64, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_i64(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_i64(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -507,7 +552,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_u32(value: u32, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_u32(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_u32(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -516,7 +561,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_u64(value: u64, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_u64(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_u64(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -525,7 +570,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_f32(value: f32, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_f32(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_f32(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -534,7 +579,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_f64(value: f64, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::decrypt_f64(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::decrypt_f64(value, key)).unwrap_or(value)
 }
 
 #[unsafe(no_mangle)]
@@ -551,7 +596,7 @@ pub unsafe extern "C" fn bacy_table_decrypt_string(
         return BacyErrorCode::NullPointer as i32;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    let result = catch_unwind(|| bacy::crypto::table::decrypt_string(value, key));
+    let result = catch_unwind(|| bacy_core::crypto::table::decrypt_string(value, key));
     match result {
         Ok(Ok(s)) => {
             *out = to_c_string(s);
@@ -562,13 +607,71 @@ pub unsafe extern "C" fn bacy_table_decrypt_string(
     }
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_i32(value: i32, key: *const u8, key_len: usize) -> i32 {
+    if key.is_null() {
+        return value;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    catch_unwind(|| bacy_core::crypto::table::encrypt_i32(value, key)).unwrap_or(value)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_i64(value: i64, key: *const u8, key_len: usize) -> i64 {
+    if key.is_null() {
+        return value;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    catch_unwind(|| bacy_core::crypto::table::encrypt_i64(value, key)).unwrap_or(value)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_u32(value: u32, key: *const u8, key_len: usize) -> u32 {
+    if key.is_null() {
+        return value;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    catch_unwind(|| bacy_core::crypto::table::encrypt_u32(value, key)).unwrap_or(value)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_u64(value: u64, key: *const u8, key_len: usize) -> u64 {
+    if key.is_null() {
+        return value;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    catch_unwind(|| bacy_core::crypto::table::encrypt_u64(value, key)).unwrap_or(value)
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn bacy_table_encrypt_f32(value: f32, key: *const u8, key_len: usize) -> f32 {
     if key.is_null() {
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::encrypt_f32(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::encrypt_f32(value, key).unwrap_or(value))
+        .unwrap_or(value)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_f32_checked(
+    value: f32,
+    key: *const u8,
+    key_len: usize,
+    out_value: *mut f32
+) -> i32 {
+    if key.is_null() || out_value.is_null() {
+        return BacyErrorCode::NullPointer as i32;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    match catch_unwind(|| bacy_core::crypto::table::encrypt_f32(value, key)) {
+        Ok(Ok(v)) => {
+            *out_value = v;
+            BacyErrorCode::Success as i32
+        }
+        Ok(Err(e)) => BacyErrorCode::from(&e) as i32,
+        Err(_) => BacyErrorCode::PanicUnwind as i32
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -577,7 +680,29 @@ pub unsafe extern "C" fn bacy_table_encrypt_f64(value: f64, key: *const u8, key_
         return value;
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    catch_unwind(|| bacy::crypto::table::encrypt_f64(value, key)).unwrap_or(value)
+    catch_unwind(|| bacy_core::crypto::table::encrypt_f64(value, key).unwrap_or(value))
+        .unwrap_or(value)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bacy_table_encrypt_f64_checked(
+    value: f64,
+    key: *const u8,
+    key_len: usize,
+    out_value: *mut f64
+) -> i32 {
+    if key.is_null() || out_value.is_null() {
+        return BacyErrorCode::NullPointer as i32;
+    }
+    let key = std::slice::from_raw_parts(key, key_len);
+    match catch_unwind(|| bacy_core::crypto::table::encrypt_f64(value, key)) {
+        Ok(Ok(v)) => {
+            *out_value = v;
+            BacyErrorCode::Success as i32
+        }
+        Ok(Err(e)) => BacyErrorCode::from(&e) as i32,
+        Err(_) => BacyErrorCode::PanicUnwind as i32
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -591,7 +716,7 @@ pub unsafe extern "C" fn bacy_table_encrypt_string(
         return ptr::null_mut();
     }
     let key = std::slice::from_raw_parts(key, key_len);
-    match catch_unwind(|| bacy::crypto::table::encrypt_string(value, key)) {
+    match catch_unwind(|| bacy_core::crypto::table::encrypt_string(value, key)) {
         Ok(s) => to_c_string(s),
         Err(_) => ptr::null_mut()
     }
@@ -608,7 +733,7 @@ pub unsafe extern "C" fn bacy_get_file_path(
     let Some(path) = str_from_ptr(path) else { return ptr::null_mut() };
     let crc = if has_crc { Some(crc) } else { None };
     let result = catch_unwind(|| {
-        bacy::utils::strategy::get_file_path(path, crc, no_hash, to_lower)
+        bacy_core::utils::strategy::get_file_path(path, crc, no_hash, to_lower)
             .to_string_lossy()
             .to_string()
     });
@@ -0,0 +1,88 @@
+use std::hint::black_box;
+
+use bacy::crypto::stream::{Keystream, TableKeystream};
+use bacy::crypto::{table, xor};
+use bacy::hash::xxhash;
+use bacy::math::mersenne::MersenneTwister;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+const SIZES: [usize; 3] = [4 << 10, 1 << 20, 64 << 20];
+const KEY: &[u8] = b"\x4D\x61\x6E\x69\x66\x65\x73\x74";
+
+fn legacy_xor_inplace(data: &mut [u8], key: &[u8]) {
+    data.iter_mut().zip(key.iter().cycle()).for_each(|(d, k)| *d ^= k);
+}
+
+fn xor_inplace(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xor_inplace");
+
+    for size in SIZES {
+        let mut data = vec![0xA5u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("legacy", size), &size, |b, _| {
+            b.iter(|| legacy_xor_inplace(black_box(&mut data), black_box(KEY)))
+        });
+        group.bench_with_input(BenchmarkId::new("current", size), &size, |b, _| {
+            b.iter(|| xor::xor_inplace(black_box(&mut data), black_box(KEY)))
+        });
+    }
+
+    group.finish();
+}
+
+fn legacy_encrypt(data: &mut [u8], key_byte: u8) {
+    data.iter_mut().for_each(|byte| *byte ^= key_byte);
+}
+
+fn encrypt(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encrypt");
+
+    for size in SIZES {
+        let mut data = vec![0xA5u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("legacy", size), &size, |b, _| {
+            b.iter(|| legacy_encrypt(black_box(&mut data), black_box(0xD9)))
+        });
+        group.bench_with_input(BenchmarkId::new("current", size), &size, |b, _| {
+            b.iter(|| xor::encrypt(black_box(&mut data), 0, size))
+        });
+    }
+
+    group.finish();
+}
+
+fn legacy_table_xor(name: &str, data: &mut [u8]) {
+    let mut mt = MersenneTwister::new(xxhash::calculate_hash_str(name));
+    let (chunks, remainder) = data.as_chunks_mut::<4>();
+    for chunk in chunks {
+        *chunk = (mt.next_u32() ^ u32::from_le_bytes(*chunk)).to_le_bytes();
+    }
+    let key = mt.next_u32().to_le_bytes();
+    remainder.iter_mut().zip(key).for_each(|(byte, k)| *byte ^= k);
+}
+
+fn table_xor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("table_xor");
+
+    for size in SIZES {
+        let mut data = vec![0xA5u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("legacy", size), &size, |b, _| {
+            b.iter(|| legacy_table_xor(black_box("CharacterExcel"), black_box(&mut data)))
+        });
+        group.bench_with_input(BenchmarkId::new("current", size), &size, |b, _| {
+            b.iter(|| table::xor(black_box("CharacterExcel"), black_box(&mut data)))
+        });
+        group.bench_with_input(BenchmarkId::new("keystream", size), &size, |b, _| {
+            b.iter(|| TableKeystream::new(black_box("CharacterExcel")).apply(black_box(&mut data)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, xor_inplace, encrypt, table_xor);
+criterion_main!(benches);
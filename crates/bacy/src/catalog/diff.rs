@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::catalog::media::MediaCatalog;
+use crate::catalog::table::TableCatalog;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct EntryState {
+    pub size: i64,
+    pub crc: i64
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DiffEntry {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub state: EntryState
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChangedEntry {
+    pub name: String,
+    pub old: EntryState,
+    pub new: EntryState
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CatalogDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub changed: Vec<ChangedEntry>
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ChangeSet {
+    pub tables: CatalogDiff,
+    pub media: CatalogDiff
+}
+
+impl CatalogDiff {
+    /// Compares entries keyed by name using the size and CRC stored in each
+    /// catalog. Results are sorted by name.
+    pub fn between<'a>(
+        old: impl IntoIterator<Item = (&'a str, EntryState)>,
+        new: impl IntoIterator<Item = (&'a str, EntryState)>
+    ) -> Self {
+        let mut old: BTreeMap<_, _> = old.into_iter().collect();
+        let mut diff = Self::default();
+
+        for (name, state) in new.into_iter().collect::<BTreeMap<_, _>>() {
+            match old.remove(name) {
+                None => diff.added.push(DiffEntry {
+                    name: name.to_string(),
+                    state
+                }),
+                Some(previous) if previous != state => diff.changed.push(ChangedEntry {
+                    name: name.to_string(),
+                    old: previous,
+                    new: state
+                }),
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = old
+            .into_iter()
+            .map(|(name, state)| DiffEntry {
+                name: name.to_string(),
+                state
+            })
+            .collect();
+
+        diff
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize { self.added.len() + self.removed.len() + self.changed.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl ChangeSet {
+    pub fn between(
+        old_tables: &TableCatalog,
+        new_tables: &TableCatalog,
+        old_media: &MediaCatalog,
+        new_media: &MediaCatalog
+    ) -> Self {
+        Self {
+            tables: old_tables.diff(new_tables),
+            media: old_media.diff(new_media)
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.tables.is_empty() && self.media.is_empty() }
+}
+
+impl TableCatalog {
+    pub fn diff(&self, newer: &TableCatalog) -> CatalogDiff {
+        CatalogDiff::between(table_states(self), table_states(newer))
+    }
+}
+
+impl MediaCatalog {
+    pub fn diff(&self, newer: &MediaCatalog) -> CatalogDiff {
+        CatalogDiff::between(media_states(self), media_states(newer))
+    }
+}
+
+fn table_states(catalog: &TableCatalog) -> impl Iterator<Item = (&str, EntryState)> {
+    catalog.iter().map(|(name, bundle)| {
+        (name, EntryState {
+            size: bundle.size,
+            crc: bundle.crc
+        })
+    })
+}
+
+fn media_states(catalog: &MediaCatalog) -> impl Iterator<Item = (&str, EntryState)> {
+    catalog.iter().map(|(path, media)| {
+        (path, EntryState {
+            size: media.size,
+            crc: media.crc
+        })
+    })
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::catalog::write_map_or_null;
+use crate::error::{CatalogError, MemoryPackError};
+use crate::format::memorypack::{
+    self,
+    FromMemoryPack,
+    MemoryPackReader,
+    MemoryPackWriter,
+    StringEncoding,
+    ToMemoryPack
+};
+use crate::hash::crc;
+use crate::utils::strategy::FileNaming;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    #[default]
+    None,
+    Audio,
+    Video,
+    Texture,
+    Unknown(i32)
+}
+
+impl From<i32> for MediaType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Audio,
+            2 => Self::Video,
+            3 => Self::Texture,
+            other => Self::Unknown(other)
+        }
+    }
+}
+
+impl From<MediaType> for i32 {
+    fn from(value: MediaType) -> Self {
+        match value {
+            MediaType::None => 0,
+            MediaType::Audio => 1,
+            MediaType::Video => 2,
+            MediaType::Texture => 3,
+            MediaType::Unknown(other) => other
+        }
+    }
+}
+
+const MEDIA_MEMBERS: u8 = 7;
+const CATALOG_MEMBERS: u8 = 1;
+
+/// Null strings and short member lists survive a read/write round trip, as
+/// for [`TableBundle`](crate::catalog::table::TableBundle).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Media {
+    pub path: Option<String>,
+    pub file_name: Option<String>,
+    pub size: i64,
+    pub crc: i64,
+    pub is_prologue: bool,
+    pub is_split_download: bool,
+    pub media_type: MediaType,
+    members: Option<u8>
+}
+
+impl Media {
+    #[inline]
+    pub fn path(&self) -> &str { self.path.as_deref().unwrap_or_default() }
+
+    #[inline]
+    pub fn file_name(&self) -> &str { self.file_name.as_deref().unwrap_or_default() }
+
+    /// Location of the downloaded file below `root`, named `<hash>_<crc>`.
+    pub fn resolve(&self, root: impl AsRef<Path>) -> PathBuf {
+        root.as_ref().join(FileNaming::default().path_for(self.path(), self.crc))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MediaCatalog {
+    entries: Vec<(String, Media)>,
+    by_path: HashMap<String, usize>,
+    encoding: StringEncoding,
+    layout: CatalogLayout
+}
+
+/// How the catalog object itself was laid out on the wire.
+#[derive(Clone, Copy, Debug, Default)]
+struct CatalogLayout {
+    members: Option<u8>,
+    null_object: bool,
+    null_entries: bool
+}
+
+impl MediaCatalog {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(CatalogError::InvalidPath);
+        }
+
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Result<Self, CatalogError> {
+        Ok(memorypack::from_bytes(data)?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CatalogError> {
+        let mut writer = MemoryPackWriter::with_encoding(self.encoding);
+        self.to_memorypack(&mut writer)?;
+        Ok(writer.into_inner())
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CatalogError> {
+        fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Media> {
+        self.entries.iter().map(|(_, media)| media)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Media)> {
+        self.entries.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    pub fn get(&self, path: &str) -> Option<&Media> {
+        self.by_path.get(&path.to_lowercase()).map(|&index| &self.entries[index].1)
+    }
+
+    pub fn of_type(&self, media_type: MediaType) -> impl Iterator<Item = &Media> {
+        self.entries().filter(move |media| media.media_type == media_type)
+    }
+
+    pub fn resolve(&self, path: &str, root: impl AsRef<Path>) -> Option<PathBuf> {
+        self.get(path).map(|media| media.resolve(root))
+    }
+
+    /// Sets the size and CRC of an entry. Returns `false` if the path is not
+    /// in the catalog.
+    pub fn update(&mut self, path: &str, size: i64, crc: i64) -> bool {
+        let Some(&index) = self.by_path.get(&path.to_lowercase()) else { return false };
+        let media = &mut self.entries[index].1;
+        media.size = size;
+        media.crc = crc;
+        media.members = media.members.map(|count| count.max(4));
+        true
+    }
+
+    #[inline]
+    pub fn update_bytes(&mut self, path: &str, data: &[u8]) -> bool {
+        self.update(path, data.len() as i64, crc::compute_bytes(data, None) as i64)
+    }
+
+    pub fn update_file(
+        &mut self,
+        path: &str,
+        file: impl AsRef<Path>
+    ) -> Result<bool, CatalogError> {
+        let file = file.as_ref();
+        let crc = crc::compute_streaming(file, 0x2000, None)?;
+        let size = fs::metadata(file)?.len();
+        Ok(self.update(path, size as i64, crc as i64))
+    }
+
+    fn from_parts(entries: Vec<(String, Media)>) -> Self {
+        let mut by_path = HashMap::with_capacity(entries.len() * 2);
+
+        for (index, (key, media)) in entries.iter().enumerate() {
+            by_path.insert(key.to_lowercase(), index);
+            by_path.entry(media.path().to_lowercase()).or_insert(index);
+        }
+
+        Self {
+            entries,
+            by_path,
+            encoding: StringEncoding::default(),
+            layout: CatalogLayout::default()
+        }
+    }
+}
+
+impl FromMemoryPack for MediaCatalog {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        let Some(count) = reader.read_optional_members("MediaCatalog", CATALOG_MEMBERS)? else {
+            return Ok(Self {
+                layout: CatalogLayout {
+                    null_object: true,
+                    ..CatalogLayout::default()
+                },
+                ..Self::default()
+            });
+        };
+
+        let entries = if count >= 1 { reader.read_optional_map()? } else { Some(Vec::new()) };
+        Ok(Self {
+            encoding: reader.string_encoding().unwrap_or_default(),
+            layout: CatalogLayout {
+                members: Some(count),
+                null_object: false,
+                null_entries: entries.is_none()
+            },
+            ..Self::from_parts(entries.unwrap_or_default())
+        })
+    }
+}
+
+impl ToMemoryPack for MediaCatalog {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        let layout = self.layout;
+        if layout.null_object {
+            writer.write_null_object();
+            return Ok(());
+        }
+
+        let count = layout.members.unwrap_or(CATALOG_MEMBERS);
+        writer.write_object_header(count)?;
+        if count >= 1 {
+            write_map_or_null(writer, &self.entries, layout.null_entries)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromMemoryPack for Media {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        let count = reader.read_members("Media", MEDIA_MEMBERS)?;
+
+        let mut media = Self {
+            members: Some(count),
+            ..Self::default()
+        };
+        for member in 0..count {
+            match member {
+                0 => media.path = reader.read()?,
+                1 => media.file_name = reader.read()?,
+                2 => media.size = reader.read()?,
+                3 => media.crc = reader.read()?,
+                4 => media.is_prologue = reader.read()?,
+                5 => media.is_split_download = reader.read()?,
+                _ => media.media_type = reader.read_i32()?.into()
+            }
+        }
+
+        Ok(media)
+    }
+}
+
+impl ToMemoryPack for Media {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        let count = self.members.unwrap_or(MEDIA_MEMBERS);
+        writer.write_object_header(count)?;
+        for member in 0..count {
+            match member {
+                0 => writer.write(&self.path)?,
+                1 => writer.write(&self.file_name)?,
+                2 => writer.write(&self.size)?,
+                3 => writer.write(&self.crc)?,
+                4 => writer.write(&self.is_prologue)?,
+                5 => writer.write(&self.is_split_download)?,
+                _ => writer.write(&i32::from(self.media_type))?
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::error::MemoryPackError;
+use crate::format::memorypack::{MemoryPackWriter, ToMemoryPack};
+
+pub mod diff;
+pub mod media;
+pub mod table;
+pub mod verify;
+
+/// Writes `entries`, or a null collection if it was null on read and is
+/// still empty.
+pub(crate) fn write_map_or_null<K: ToMemoryPack, V: ToMemoryPack>(
+    writer: &mut MemoryPackWriter,
+    entries: &[(K, V)],
+    null: bool
+) -> Result<(), MemoryPackError> {
+    if null && entries.is_empty() {
+        writer.write_null_collection();
+        return Ok(());
+    }
+    writer.write_map(entries)
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::catalog::write_map_or_null;
+use crate::error::{CatalogError, MemoryPackError};
+use crate::format::memorypack::{
+    self,
+    FromMemoryPack,
+    MemoryPackReader,
+    MemoryPackWriter,
+    StringEncoding,
+    ToMemoryPack
+};
+use crate::hash::crc;
+
+const BUNDLE_MEMBERS: u8 = 8;
+const PACK_MEMBERS: u8 = 5;
+const CATALOG_MEMBERS: u8 = 2;
+
+/// Nullable members stay `Option` and the member count read from the wire is
+/// kept, so unchanged entries write back byte for byte.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableBundle {
+    pub name: Option<String>,
+    pub size: i64,
+    pub crc: i64,
+    pub is_in_build: bool,
+    pub is_changed: bool,
+    pub is_prologue: bool,
+    pub is_split_download: bool,
+    pub includes: Option<Vec<String>>,
+    members: Option<u8>
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TablePatchPack {
+    pub name: Option<String>,
+    pub size: i64,
+    pub crc: i64,
+    pub is_prologue: bool,
+    pub bundle_files: Option<Vec<TableBundle>>,
+    members: Option<u8>
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TableCatalog {
+    bundles: Vec<(String, TableBundle)>,
+    packs: Vec<(String, TablePatchPack)>,
+    by_name: HashMap<String, usize>,
+    by_table: HashMap<String, usize>,
+    encoding: StringEncoding,
+    layout: CatalogLayout
+}
+
+/// How the catalog object itself was laid out on the wire.
+#[derive(Clone, Copy, Debug, Default)]
+struct CatalogLayout {
+    members: Option<u8>,
+    null_object: bool,
+    null_bundles: bool,
+    null_packs: bool
+}
+
+impl TableBundle {
+    #[inline]
+    pub fn name(&self) -> &str { self.name.as_deref().unwrap_or_default() }
+
+    #[inline]
+    pub fn includes(&self) -> &[String] { self.includes.as_deref().unwrap_or_default() }
+
+    fn set_size_crc(&mut self, size: i64, crc: i64) {
+        self.size = size;
+        self.crc = crc;
+        self.members = self.members.map(|count| count.max(3));
+    }
+}
+
+impl TablePatchPack {
+    #[inline]
+    pub fn name(&self) -> &str { self.name.as_deref().unwrap_or_default() }
+
+    #[inline]
+    pub fn bundle_files(&self) -> &[TableBundle] {
+        self.bundle_files.as_deref().unwrap_or_default()
+    }
+}
+
+impl TableCatalog {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(CatalogError::InvalidPath);
+        }
+
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Result<Self, CatalogError> {
+        Ok(memorypack::from_bytes(data)?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CatalogError> {
+        let mut writer = MemoryPackWriter::with_encoding(self.encoding);
+        self.to_memorypack(&mut writer)?;
+        Ok(writer.into_inner())
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CatalogError> {
+        fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    pub fn bundles(&self) -> impl Iterator<Item = &TableBundle> {
+        self.bundles.iter().map(|(_, bundle)| bundle)
+    }
+
+    pub fn packs(&self) -> impl Iterator<Item = &TablePatchPack> {
+        self.packs.iter().map(|(_, pack)| pack)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TableBundle)> {
+        self.bundles.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize { self.bundles.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.bundles.is_empty() }
+
+    pub fn bundle(&self, name: &str) -> Option<&TableBundle> {
+        self.by_name.get(name).map(|&index| &self.bundles[index].1)
+    }
+
+    pub fn bundle_for_table(&self, table_name: &str) -> Option<&TableBundle> {
+        self.by_table.get(&table_name.to_lowercase()).map(|&index| &self.bundles[index].1)
+    }
+
+    pub fn pack(&self, name: &str) -> Option<&TablePatchPack> {
+        self.packs.iter().find(|(key, _)| key == name).map(|(_, pack)| pack)
+    }
+
+    /// Sets the size and CRC of a bundle, including its copies inside patch
+    /// packs. Returns `false` if the bundle is not in the catalog.
+    pub fn update(&mut self, name: &str, size: i64, crc: i64) -> bool {
+        let Some(&index) = self.by_name.get(name) else { return false };
+        let bundle = &mut self.bundles[index].1;
+        bundle.set_size_crc(size, crc);
+
+        let name = &bundle.name;
+        self.packs
+            .iter_mut()
+            .flat_map(|(_, pack)| pack.bundle_files.iter_mut().flatten())
+            .filter(|file| file.name == *name)
+            .for_each(|file| file.set_size_crc(size, crc));
+        true
+    }
+
+    #[inline]
+    pub fn update_bytes(&mut self, name: &str, data: &[u8]) -> bool {
+        self.update(name, data.len() as i64, crc::compute_bytes(data, None) as i64)
+    }
+
+    pub fn update_file(
+        &mut self,
+        name: &str,
+        path: impl AsRef<Path>
+    ) -> Result<bool, CatalogError> {
+        let path = path.as_ref();
+        let crc = crc::compute_streaming(path, 0x2000, None)?;
+        let size = fs::metadata(path)?.len();
+        Ok(self.update(name, size as i64, crc as i64))
+    }
+
+    fn from_parts(
+        bundles: Vec<(String, TableBundle)>,
+        packs: Vec<(String, TablePatchPack)>
+    ) -> Self {
+        let mut by_name = HashMap::with_capacity(bundles.len());
+        let mut by_table = HashMap::new();
+
+        for (index, (key, bundle)) in bundles.iter().enumerate() {
+            by_name.insert(key.clone(), index);
+            for table in bundle.includes() {
+                by_table.insert(table.to_lowercase(), index);
+            }
+        }
+
+        Self {
+            bundles,
+            packs,
+            by_name,
+            by_table,
+            encoding: StringEncoding::default(),
+            layout: CatalogLayout::default()
+        }
+    }
+}
+
+impl FromMemoryPack for TableCatalog {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        let Some(count) = reader.read_optional_members("TableCatalog", CATALOG_MEMBERS)? else {
+            return Ok(Self {
+                layout: CatalogLayout {
+                    null_object: true,
+                    ..CatalogLayout::default()
+                },
+                ..Self::default()
+            });
+        };
+
+        let bundles = if count >= 1 { reader.read_optional_map()? } else { Some(Vec::new()) };
+        let packs = if count >= 2 { reader.read_optional_map()? } else { Some(Vec::new()) };
+
+        Ok(Self {
+            encoding: reader.string_encoding().unwrap_or_default(),
+            layout: CatalogLayout {
+                members: Some(count),
+                null_object: false,
+                null_bundles: bundles.is_none(),
+                null_packs: packs.is_none()
+            },
+            ..Self::from_parts(bundles.unwrap_or_default(), packs.unwrap_or_default())
+        })
+    }
+}
+
+impl ToMemoryPack for TableCatalog {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        let layout = self.layout;
+        if layout.null_object {
+            writer.write_null_object();
+            return Ok(());
+        }
+
+        let count = layout.members.unwrap_or(CATALOG_MEMBERS);
+        writer.write_object_header(count)?;
+        if count >= 1 {
+            write_map_or_null(writer, &self.bundles, layout.null_bundles)?;
+        }
+        if count >= 2 {
+            write_map_or_null(writer, &self.packs, layout.null_packs)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromMemoryPack for TableBundle {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        let count = reader.read_members("TableBundle", BUNDLE_MEMBERS)?;
+
+        let mut bundle = Self {
+            members: Some(count),
+            ..Self::default()
+        };
+        for member in 0..count {
+            match member {
+                0 => bundle.name = reader.read()?,
+                1 => bundle.size = reader.read()?,
+                2 => bundle.crc = reader.read()?,
+                3 => bundle.is_in_build = reader.read()?,
+                4 => bundle.is_changed = reader.read()?,
+                5 => bundle.is_prologue = reader.read()?,
+                6 => bundle.is_split_download = reader.read()?,
+                _ => bundle.includes = reader.read()?
+            }
+        }
+
+        Ok(bundle)
+    }
+}
+
+impl FromMemoryPack for TablePatchPack {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        let count = reader.read_members("TablePatchPack", PACK_MEMBERS)?;
+
+        let mut pack = Self {
+            members: Some(count),
+            ..Self::default()
+        };
+        for member in 0..count {
+            match member {
+                0 => pack.name = reader.read()?,
+                1 => pack.size = reader.read()?,
+                2 => pack.crc = reader.read()?,
+                3 => pack.is_prologue = reader.read()?,
+                _ => pack.bundle_files = reader.read()?
+            }
+        }
+
+        Ok(pack)
+    }
+}
+
+impl ToMemoryPack for TableBundle {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        let count = self.members.unwrap_or(BUNDLE_MEMBERS);
+        writer.write_object_header(count)?;
+        for member in 0..count {
+            match member {
+                0 => writer.write(&self.name)?,
+                1 => writer.write(&self.size)?,
+                2 => writer.write(&self.crc)?,
+                3 => writer.write(&self.is_in_build)?,
+                4 => writer.write(&self.is_changed)?,
+                5 => writer.write(&self.is_prologue)?,
+                6 => writer.write(&self.is_split_download)?,
+                _ => writer.write(&self.includes)?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToMemoryPack for TablePatchPack {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        let count = self.members.unwrap_or(PACK_MEMBERS);
+        writer.write_object_header(count)?;
+        for member in 0..count {
+            match member {
+                0 => writer.write(&self.name)?,
+                1 => writer.write(&self.size)?,
+                2 => writer.write(&self.crc)?,
+                3 => writer.write(&self.is_prologue)?,
+                _ => writer.write(&self.bundle_files)?
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::{BTreeSet, HashSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{fs, io, thread};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::catalog::media::MediaCatalog;
+use crate::catalog::table::TableCatalog;
+use crate::error::CatalogError;
+use crate::hash::crc;
+use crate::utils::strategy::FileNaming;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ExpectedFile {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: i64,
+    pub crc: i64
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CorruptedFile {
+    pub file: ExpectedFile,
+    pub actual_size: u64,
+    /// `None` when the size already mismatched and the file was not hashed.
+    pub actual_crc: Option<u32>
+}
+
+/// A file that could not be checked, e.g. because it was not readable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FailedFile {
+    pub file: ExpectedFile,
+    pub error: String
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerifyReport {
+    pub verified: usize,
+    pub missing: Vec<ExpectedFile>,
+    pub corrupted: Vec<CorruptedFile>,
+    pub failed: Vec<FailedFile>,
+    pub unexpected: Vec<PathBuf>
+}
+
+impl VerifyReport {
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+            && self.corrupted.is_empty()
+            && self.failed.is_empty()
+            && self.unexpected.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyProgress<'a> {
+    pub checked: usize,
+    pub total: usize,
+    pub path: &'a Path
+}
+
+enum Outcome {
+    Verified,
+    Missing,
+    Corrupted { actual_size: u64, actual_crc: Option<u32> }
+}
+
+pub struct Verifier {
+    root: PathBuf,
+    files: Vec<ExpectedFile>,
+    threads: usize
+}
+
+impl Verifier {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            files: Vec::new(),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get)
+        }
+    }
+
+    #[inline]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Adds table bundles, relative to `dir` below the root.
+    pub fn with_tables(
+        mut self,
+        catalog: &TableCatalog,
+        dir: impl AsRef<Path>,
+        naming: FileNaming
+    ) -> Self {
+        let dir = dir.as_ref();
+        self.files.extend(catalog.iter().map(|(name, bundle)| ExpectedFile {
+            name: name.to_string(),
+            path: dir.join(naming.path_for(name, bundle.crc)),
+            size: bundle.size,
+            crc: bundle.crc
+        }));
+        self
+    }
+
+    /// Adds media files, relative to `dir` below the root.
+    pub fn with_media(
+        mut self,
+        catalog: &MediaCatalog,
+        dir: impl AsRef<Path>,
+        naming: FileNaming
+    ) -> Self {
+        let dir = dir.as_ref();
+        self.files.extend(catalog.entries().map(|media| ExpectedFile {
+            name: media.path().to_string(),
+            path: dir.join(naming.path_for(media.path(), media.crc)),
+            size: media.size,
+            crc: media.crc
+        }));
+        self
+    }
+
+    #[inline]
+    pub fn with_file(mut self, file: ExpectedFile) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    #[inline]
+    pub fn files(&self) -> &[ExpectedFile] { &self.files }
+
+    /// Checks every expected file on a pool of worker threads. `progress` runs
+    /// on the calling thread after each file. Files that fail to read end up
+    /// in [`VerifyReport::failed`], and only the directories holding expected
+    /// files are searched for unexpected ones.
+    pub fn verify(
+        &self,
+        mut progress: impl FnMut(VerifyProgress<'_>)
+    ) -> Result<VerifyReport, CatalogError> {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        let mut report = VerifyReport::default();
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(self.files.len()) {
+                let sender = sender.clone();
+                let next = &next;
+                scope.spawn(move || {
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(file) = self.files.get(index) else { break };
+
+                        let outcome = check(&self.root.join(&file.path), file);
+                        if sender.send((index, outcome)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            for (checked, (index, outcome)) in receiver.into_iter().enumerate() {
+                let file = &self.files[index];
+                match outcome {
+                    Ok(Outcome::Verified) => report.verified += 1,
+                    Ok(Outcome::Missing) => report.missing.push(file.clone()),
+                    Ok(Outcome::Corrupted { actual_size, actual_crc }) => {
+                        report.corrupted.push(CorruptedFile {
+                            file: file.clone(),
+                            actual_size,
+                            actual_crc
+                        })
+                    }
+                    Err(e) => report.failed.push(FailedFile {
+                        file: file.clone(),
+                        error: e.to_string()
+                    })
+                }
+
+                progress(VerifyProgress {
+                    checked: checked + 1,
+                    total: self.files.len(),
+                    path: &file.path
+                });
+            }
+        });
+
+        let expected: HashSet<_> =
+            self.files.iter().map(|file| self.root.join(&file.path)).collect();
+        let dirs: BTreeSet<_> = expected.iter().filter_map(|path| path.parent()).collect();
+        for dir in dirs {
+            list_files(dir, &mut |path| {
+                if !expected.contains(path) {
+                    let relative = path.strip_prefix(&self.root).unwrap_or(path);
+                    report.unexpected.push(relative.to_path_buf());
+                }
+            })?;
+        }
+
+        report.missing.sort_by(|a, b| a.path.cmp(&b.path));
+        report.corrupted.sort_by(|a, b| a.file.path.cmp(&b.file.path));
+        report.failed.sort_by(|a, b| a.file.path.cmp(&b.file.path));
+        report.unexpected.sort();
+        Ok(report)
+    }
+}
+
+fn check(path: &Path, file: &ExpectedFile) -> Result<Outcome, CatalogError> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        Ok(_) => return Ok(Outcome::Missing),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Outcome::Missing),
+        Err(e) => return Err(e.into())
+    };
+
+    let actual_size = metadata.len();
+    if actual_size as i64 != file.size {
+        return Ok(Outcome::Corrupted {
+            actual_size,
+            actual_crc: None
+        });
+    }
+
+    let actual_crc = crc::compute_streaming(path, 0x10000, None)?;
+    if actual_crc as i64 == file.crc {
+        Ok(Outcome::Verified)
+    } else {
+        Ok(Outcome::Corrupted {
+            actual_size,
+            actual_crc: Some(actual_crc)
+        })
+    }
+}
+
+/// Visits the files directly inside `dir`, subdirectories are skipped.
+fn list_files(dir: &Path, visit: &mut impl FnMut(&Path)) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::NotADirectory) => {
+            return Ok(());
+        }
+        Err(e) => return Err(e)
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            visit(&entry.path());
+        }
+    }
+    Ok(())
+}
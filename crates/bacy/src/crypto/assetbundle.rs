@@ -0,0 +1,248 @@
+use std::io::{self, Read, Write};
+use std::ops::Range;
+
+use crate::crypto::xor::{ENCRYPTION_KEY, encrypt_with};
+use crate::error::AssetBundleError;
+
+const SIGNATURE: &[u8; 8] = b"UnityFS\0";
+const FORMAT_VERSION_SIZE: usize = 4;
+const HEADER_TAIL_SIZE: usize = 20;
+const MAX_HEADER_SIZE: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleState {
+    Plain,
+    Encrypted,
+    Unknown
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AssetBundleCipher {
+    key: u32
+}
+
+impl Default for AssetBundleCipher {
+    fn default() -> Self { Self::new(ENCRYPTION_KEY) }
+}
+
+impl AssetBundleCipher {
+    #[inline]
+    pub const fn new(key: u32) -> Self { Self { key } }
+
+    #[inline]
+    pub const fn key(&self) -> u32 { self.key }
+
+    #[inline]
+    fn key_byte(&self) -> u8 { (self.key & 0xFF) as u8 }
+
+    /// Whether `data` can still grow into a plain or encrypted signature.
+    fn signature_prefix(&self, data: &[u8]) -> bool {
+        let key_byte = self.key_byte();
+        SIGNATURE.starts_with(data) || data.iter().zip(SIGNATURE).all(|(d, s)| d ^ key_byte == *s)
+    }
+
+    pub fn detect(&self, data: &[u8]) -> BundleState {
+        if data.len() < SIGNATURE.len() {
+            return BundleState::Unknown;
+        }
+
+        let key_byte = self.key_byte();
+        if data.starts_with(SIGNATURE) {
+            BundleState::Plain
+        } else if data.iter().zip(SIGNATURE).all(|(d, s)| d ^ key_byte == *s) {
+            BundleState::Encrypted
+        } else {
+            BundleState::Unknown
+        }
+    }
+
+    pub fn obfuscated_range(&self, data: &[u8]) -> Result<Range<usize>, AssetBundleError> {
+        let key_byte = match self.detect(data) {
+            BundleState::Plain => 0,
+            BundleState::Encrypted => self.key_byte(),
+            BundleState::Unknown => return Err(AssetBundleError::NotAssetBundle)
+        };
+
+        header_len(data, key_byte).map(|len| 0..len)
+    }
+
+    pub fn decrypt(&self, data: &mut [u8]) -> Result<BundleState, AssetBundleError> {
+        let state = self.detect(data);
+        match state {
+            BundleState::Plain => {}
+            BundleState::Encrypted => self.toggle(data)?,
+            BundleState::Unknown => return Err(AssetBundleError::NotAssetBundle)
+        }
+        Ok(state)
+    }
+
+    pub fn encrypt(&self, data: &mut [u8]) -> Result<BundleState, AssetBundleError> {
+        let state = self.detect(data);
+        match state {
+            BundleState::Plain => self.toggle(data)?,
+            BundleState::Encrypted => {}
+            BundleState::Unknown => return Err(AssetBundleError::NotAssetBundle)
+        }
+        Ok(state)
+    }
+
+    pub fn reader<R: Read>(&self, inner: R) -> Result<AssetBundleReader<R>, AssetBundleError> {
+        AssetBundleReader::new(*self, inner)
+    }
+
+    #[inline]
+    pub fn writer<W: Write>(&self, inner: W) -> AssetBundleWriter<W> {
+        AssetBundleWriter::new(*self, inner)
+    }
+
+    fn toggle(&self, data: &mut [u8]) -> Result<(), AssetBundleError> {
+        let range = self.obfuscated_range(data)?;
+        encrypt_with(data, range.start, range.len(), self.key);
+        Ok(())
+    }
+}
+
+pub struct AssetBundleReader<R: Read> {
+    inner: R,
+    header: Vec<u8>,
+    position: usize,
+    state: BundleState
+}
+
+impl<R: Read> AssetBundleReader<R> {
+    fn new(cipher: AssetBundleCipher, mut inner: R) -> Result<Self, AssetBundleError> {
+        let mut header = Vec::with_capacity(MAX_HEADER_SIZE);
+        (&mut inner).take(MAX_HEADER_SIZE as u64).read_to_end(&mut header)?;
+
+        let state = cipher.decrypt(&mut header)?;
+        Ok(Self {
+            inner,
+            header,
+            position: 0,
+            state
+        })
+    }
+
+    #[inline]
+    pub fn state(&self) -> BundleState { self.state }
+}
+
+impl<R: Read> Read for AssetBundleReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position < self.header.len() {
+            let remaining = &self.header[self.position..];
+            let count = remaining.len().min(buf.len());
+            buf[..count].copy_from_slice(&remaining[..count]);
+            self.position += count;
+            return Ok(count);
+        }
+
+        self.inner.read(buf)
+    }
+}
+
+/// Writes a bundle with its header obfuscation toggled: encrypted input
+/// comes out plain and plain input comes out encrypted.
+///
+/// Only the header is buffered, and input that cannot start a UnityFS
+/// signature is rejected on the write that shows it. Call `finish` to find
+/// out whether the header was ever completed.
+pub struct AssetBundleWriter<W: Write> {
+    inner: W,
+    cipher: AssetBundleCipher,
+    header: Vec<u8>,
+    passthrough: bool
+}
+
+impl<W: Write> AssetBundleWriter<W> {
+    fn new(cipher: AssetBundleCipher, inner: W) -> Self {
+        Self {
+            inner,
+            cipher,
+            header: Vec::new(),
+            passthrough: false
+        }
+    }
+
+    /// Fails with `TruncatedHeader` if the input ended inside the header, in
+    /// which case nothing reached the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.passthrough {
+            return Err(invalid_data(AssetBundleError::TruncatedHeader));
+        }
+
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn try_flush_header(&mut self) -> io::Result<()> {
+        let range = match self.cipher.obfuscated_range(&self.header) {
+            Ok(range) => range,
+            Err(AssetBundleError::TruncatedHeader) if self.header.len() < MAX_HEADER_SIZE => {
+                return Ok(());
+            }
+            Err(AssetBundleError::NotAssetBundle)
+                if self.header.len() < SIGNATURE.len()
+                    && self.cipher.signature_prefix(&self.header) =>
+            {
+                return Ok(());
+            }
+            Err(e) => return Err(invalid_data(e))
+        };
+
+        encrypt_with(&mut self.header, range.start, range.len(), self.cipher.key);
+        if let Err(e) = self.inner.write_all(&self.header) {
+            // Leave the buffered header as the caller wrote it.
+            encrypt_with(&mut self.header, range.start, range.len(), self.cipher.key);
+            return Err(e);
+        }
+
+        self.header = Vec::new();
+        self.passthrough = true;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for AssetBundleWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.passthrough {
+            return self.inner.write(buf);
+        }
+
+        let count = buf.len().min(MAX_HEADER_SIZE - self.header.len());
+        self.header.extend_from_slice(&buf[..count]);
+        if let Err(e) = self.try_flush_header() {
+            self.header.truncate(self.header.len() - count);
+            return Err(e);
+        }
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+fn header_len(data: &[u8], key_byte: u8) -> Result<usize, AssetBundleError> {
+    let mut position = SIGNATURE.len() + FORMAT_VERSION_SIZE;
+
+    for _ in 0..2 {
+        loop {
+            let byte = data.get(position).ok_or(AssetBundleError::TruncatedHeader)? ^ key_byte;
+            position += 1;
+
+            if byte == 0 {
+                break;
+            }
+            if position >= MAX_HEADER_SIZE {
+                return Err(AssetBundleError::NotAssetBundle);
+            }
+        }
+    }
+
+    position += HEADER_TAIL_SIZE;
+    if position > data.len() { Err(AssetBundleError::TruncatedHeader) } else { Ok(position) }
+}
+
+#[inline]
+fn invalid_data(error: AssetBundleError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
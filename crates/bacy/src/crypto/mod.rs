@@ -1,4 +1,6 @@
 pub mod aes;
+pub mod assetbundle;
 pub mod md5;
+pub mod stream;
 pub mod table;
 pub mod xor;
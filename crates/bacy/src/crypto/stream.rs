@@ -0,0 +1,368 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::crypto::xor::ENCRYPTION_KEY;
+use crate::hash::xxhash;
+use crate::math::mersenne::{BLOCK_BYTES, MersenneBlocks, MersenneTwister};
+
+const BLOCK_WORDS: u64 = (BLOCK_BYTES / 4) as u64;
+const CHECKPOINT_BLOCKS: u64 = 1 << 7;
+
+pub trait Keystream {
+    fn apply(&mut self, data: &mut [u8]);
+
+    fn position(&self) -> u64;
+}
+
+pub trait SeekableKeystream: Keystream {
+    fn seek(&mut self, position: u64);
+
+    /// Seeks to `position` and applies the keystream from there. Reusing one
+    /// keystream keeps its cached state between calls.
+    #[inline]
+    fn apply_at(&mut self, position: u64, data: &mut [u8]) {
+        self.seek(position);
+        self.apply(data);
+    }
+}
+
+pub struct CyclicKey {
+    key: Vec<u8>,
+    position: u64
+}
+
+impl CyclicKey {
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: key.to_vec(),
+            position: 0
+        }
+    }
+}
+
+impl Keystream for CyclicKey {
+    fn apply(&mut self, data: &mut [u8]) {
+        if self.key.is_empty() {
+            self.position += data.len() as u64;
+            return;
+        }
+
+        let start = (self.position % self.key.len() as u64) as usize;
+        let key = self.key[start..].iter().chain(self.key[..start].iter()).cycle();
+        data.iter_mut().zip(key).for_each(|(d, k)| *d ^= k);
+        self.position += data.len() as u64;
+    }
+
+    #[inline]
+    fn position(&self) -> u64 { self.position }
+}
+
+impl SeekableKeystream for CyclicKey {
+    #[inline]
+    fn seek(&mut self, position: u64) { self.position = position; }
+}
+
+pub struct ByteKey {
+    key_byte: u8,
+    start: u64,
+    end: u64,
+    position: u64
+}
+
+impl Default for ByteKey {
+    fn default() -> Self { Self::new(ENCRYPTION_KEY) }
+}
+
+impl ByteKey {
+    #[inline]
+    pub fn new(key: u32) -> Self { Self::with_range(key, 0, u64::MAX) }
+
+    #[inline]
+    pub fn with_range(key: u32, offset: u64, length: u64) -> Self {
+        Self {
+            key_byte: (key & 0xFF) as u8,
+            start: offset,
+            end: offset.saturating_add(length),
+            position: 0
+        }
+    }
+}
+
+impl Keystream for ByteKey {
+    fn apply(&mut self, data: &mut [u8]) {
+        let chunk_end = self.position + data.len() as u64;
+        let from = self.start.clamp(self.position, chunk_end) - self.position;
+        let to = self.end.clamp(self.position, chunk_end) - self.position;
+
+        data[from as usize..to as usize].iter_mut().for_each(|byte| *byte ^= self.key_byte);
+        self.position = chunk_end;
+    }
+
+    #[inline]
+    fn position(&self) -> u64 { self.position }
+}
+
+impl SeekableKeystream for ByteKey {
+    #[inline]
+    fn seek(&mut self, position: u64) { self.position = position; }
+}
+
+pub struct TableKeystream {
+    seed: u32,
+    mt: MersenneTwister,
+    word: [u8; 4],
+    used: usize,
+    words: u64,
+    position: u64,
+    /// Generator states every `CHECKPOINT_BLOCKS` blocks.
+    checkpoints: Vec<MersenneBlocks>
+}
+
+impl TableKeystream {
+    pub fn new(name: &str) -> Self {
+        let seed = xxhash::calculate_hash_str(name);
+        Self {
+            seed,
+            mt: MersenneTwister::new(seed),
+            word: [0; 4],
+            used: 4,
+            words: 0,
+            position: 0,
+            checkpoints: Vec::new()
+        }
+    }
+
+    fn restore(&mut self, word: u64) {
+        if word >= self.words && word - self.words < BLOCK_WORDS {
+            self.mt.discard(word - self.words);
+            self.words = word;
+            return;
+        }
+
+        let block = word / BLOCK_WORDS;
+        let index = (block / CHECKPOINT_BLOCKS) as usize;
+        while self.checkpoints.len() <= index {
+            let checkpoint = match self.checkpoints.last() {
+                Some(last) => {
+                    let mut blocks = last.clone();
+                    blocks.skip_blocks(CHECKPOINT_BLOCKS);
+                    blocks
+                }
+                None => MersenneBlocks::new(self.seed)
+            };
+            self.checkpoints.push(checkpoint);
+        }
+
+        let mut blocks = self.checkpoints[index].clone();
+        blocks.skip_blocks(block - index as u64 * CHECKPOINT_BLOCKS);
+        self.mt = blocks.to_twister();
+        self.mt.discard(word - block * BLOCK_WORDS);
+        self.words = word;
+    }
+}
+
+impl Keystream for TableKeystream {
+    fn apply(&mut self, data: &mut [u8]) {
+        let mut data = data;
+
+        while self.used < 4 && !data.is_empty() {
+            data[0] ^= self.word[self.used];
+            self.used += 1;
+            self.position += 1;
+            data = &mut data[1..];
+        }
+
+        let (chunks, remainder) = data.as_chunks_mut::<4>();
+        self.words += chunks.len() as u64;
+        self.position += (chunks.len() * 4) as u64;
+        for chunk in chunks {
+            *chunk = (self.mt.next_u32() ^ u32::from_le_bytes(*chunk)).to_le_bytes();
+        }
+
+        if !remainder.is_empty() {
+            self.word = self.mt.next_u32().to_le_bytes();
+            self.words += 1;
+            for (i, byte) in remainder.iter_mut().enumerate() {
+                *byte ^= self.word[i];
+            }
+            self.used = remainder.len();
+            self.position += remainder.len() as u64;
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> u64 { self.position }
+}
+
+impl SeekableKeystream for TableKeystream {
+    fn seek(&mut self, position: u64) {
+        if position == self.position {
+            return;
+        }
+
+        self.restore(position / 4);
+        self.position = position;
+
+        let offset = (position % 4) as usize;
+        if offset == 0 {
+            self.used = 4;
+        } else {
+            self.word = self.mt.next_u32().to_le_bytes();
+            self.words += 1;
+            self.used = offset;
+        }
+    }
+}
+
+pub struct XorReader<R, K> {
+    inner: R,
+    keystream: K
+}
+
+impl<R, K: Keystream> XorReader<R, K> {
+    #[inline]
+    pub fn new(inner: R, keystream: K) -> Self { Self { inner, keystream } }
+
+    #[inline]
+    pub fn position(&self) -> u64 { self.keystream.position() }
+
+    #[inline]
+    pub fn get_ref(&self) -> &R { &self.inner }
+
+    #[inline]
+    pub fn into_inner(self) -> R { self.inner }
+}
+
+impl<R: Read, K: Keystream> Read for XorReader<R, K> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.keystream.apply(&mut buf[..count]);
+        Ok(count)
+    }
+}
+
+impl<R: Seek, K: SeekableKeystream> Seek for XorReader<R, K> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = self.inner.seek(pos)?;
+        self.keystream.seek(position);
+        Ok(position)
+    }
+}
+
+impl<R: AsyncRead + Unpin, K: Keystream + Unpin> AsyncRead for XorReader<R, K> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.keystream.apply(&mut buf.filled_mut()[filled..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Encrypts everything written to it before passing it on to `inner`.
+///
+/// Each `write` makes at most one call to `inner` and only reports the bytes
+/// it accepted. The keystream cannot be rewound, so bytes that were encrypted
+/// but not accepted stay pending and must be retried, as `write_all` does: the
+/// next `write` has to start with the same data and sends the pending bytes
+/// first, or fails with `InvalidInput`.
+pub struct XorWriter<W, K> {
+    inner: W,
+    keystream: K,
+    plain: Vec<u8>,
+    pending: Vec<u8>,
+    written: usize
+}
+
+impl<W, K: Keystream> XorWriter<W, K> {
+    #[inline]
+    pub fn new(inner: W, keystream: K) -> Self {
+        Self {
+            inner,
+            keystream,
+            plain: Vec::new(),
+            pending: Vec::new(),
+            written: 0
+        }
+    }
+
+    /// The number of bytes accepted so far, pending bytes excluded.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.keystream.position() - (self.pending.len() - self.written) as u64
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    #[inline]
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// Returns the range of `pending` to send for `buf`, staging it first
+    /// when nothing is pending.
+    fn prepare(&mut self, buf: &[u8]) -> io::Result<Range<usize>> {
+        if self.written == self.pending.len() {
+            self.plain.clear();
+            self.plain.extend_from_slice(buf);
+            self.pending.clear();
+            self.pending.extend_from_slice(buf);
+            self.written = 0;
+            self.keystream.apply(&mut self.pending);
+        }
+
+        let len = buf.len().min(self.pending.len() - self.written);
+        let range = self.written..self.written + len;
+        if self.plain[range.clone()] != buf[..len] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "write does not retry the pending bytes"
+            ));
+        }
+        Ok(range)
+    }
+}
+
+impl<W: Write, K: Keystream> Write for XorWriter<W, K> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let range = self.prepare(buf)?;
+        let count = self.inner.write(&self.pending[range])?;
+        self.written += count;
+        Ok(count)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+}
+
+impl<W: AsyncWrite + Unpin, K: Keystream + Unpin> AsyncWrite for XorWriter<W, K> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8]
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let range = this.prepare(buf)?;
+        let count = ready!(Pin::new(&mut this.inner).poll_write(cx, &this.pending[range]))?;
+        this.written += count;
+        Poll::Ready(Ok(count))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -1,10 +1,36 @@
+#[cfg(feature = "derive")]
+pub use bacy_derive::{TableDecrypt, TableEncrypt};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 
-use crate::crypto::xor::xor_inplace;
+use crate::crypto::stream::{SeekableKeystream, TableKeystream};
+use crate::crypto::xor::{xor_inplace, xor_slices};
 use crate::error::TableEncryptionError;
 use crate::hash::xxhash;
-use crate::math::mersenne::MersenneTwister;
+use crate::math::mersenne::{BLOCK_BYTES, MersenneBlocks, MersenneTwister};
+
+pub trait TableDecrypt {
+    fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError>;
+}
+
+pub trait TableEncrypt {
+    fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError>;
+}
+
+pub trait TableRecord: TableDecrypt {
+    const KEY_NAME: &'static str;
+
+    fn decrypt_record(&mut self) -> Result<(), TableEncryptionError> {
+        self.decrypt(&create_key(Self::KEY_NAME))
+    }
+
+    fn encrypt_record(&mut self) -> Result<(), TableEncryptionError>
+    where
+        Self: TableEncrypt
+    {
+        self.encrypt(&create_key(Self::KEY_NAME))
+    }
+}
 
 #[inline]
 pub fn create_key(name: &str) -> [u8; 8] {
@@ -28,19 +54,20 @@ pub fn create_password(key: &str, length: usize) -> String {
 
 pub fn xor(name: &str, bytes: &mut [u8]) {
     let hash = xxhash::calculate_hash_str(name);
-    let mut mt = MersenneTwister::new(hash);
+    let mut blocks = MersenneBlocks::new(hash);
 
-    let (chunks, remainder) = bytes.as_chunks_mut::<4>();
-    for chunk in chunks {
-        *chunk = (mt.next_u32() ^ u32::from_le_bytes(*chunk)).to_le_bytes();
+    let mut block = [0u8; BLOCK_BYTES];
+    for chunk in bytes.chunks_mut(BLOCK_BYTES) {
+        blocks.next_block(&mut block);
+        xor_slices(chunk, &block[..chunk.len()]);
     }
+}
 
-    if !remainder.is_empty() {
-        let xor_key = mt.next_u32().to_le_bytes();
-        for (i, byte) in remainder.iter_mut().enumerate() {
-            *byte ^= xor_key[i];
-        }
-    }
+/// One-off random access. For repeated reads keep a [`TableKeystream`] and
+/// call [`apply_at`](SeekableKeystream::apply_at), which reuses its cached
+/// checkpoints instead of regenerating from the seed.
+pub fn xor_at(name: &str, offset: u64, bytes: &mut [u8]) {
+    TableKeystream::new(name).apply_at(offset, bytes);
 }
 
 #[inline]
@@ -109,15 +136,54 @@ pub fn decrypt_string(value: &str, key: &[u8]) -> Result<String, TableEncryption
 }
 
 #[inline]
-pub fn encrypt_f32(value: f32, key: &[u8]) -> f32 {
-    let multiplier = calculate_multiplier(key[0]);
-    ((value * 10000.0) as i32 * multiplier) as f32
+pub fn encrypt_i32(value: i32, key: &[u8]) -> i32 { decrypt_i32(value, key) }
+
+#[inline]
+pub fn encrypt_i64(value: i64, key: &[u8]) -> i64 { decrypt_i64(value, key) }
+
+#[inline]
+pub fn encrypt_u32(value: u32, key: &[u8]) -> u32 { decrypt_u32(value, key) }
+
+#[inline]
+pub fn encrypt_u64(value: u64, key: &[u8]) -> u64 { decrypt_u64(value, key) }
+
+#[inline]
+pub fn encrypt_enum<T>(value: T, key: &[u8]) -> Result<T, TableEncryptionError>
+where
+    T: Copy + flatbuffers::EndianScalar,
+    T::Scalar: Into<i32> + From<i32> + Copy
+{
+    // Zero is stored unencrypted, so a value that XORs to zero cannot be read back.
+    let scalar_val: i32 = value.to_little_endian().into();
+    if scalar_val != 0 && encrypt_i32(scalar_val, key) == 0 {
+        return Err(TableEncryptionError::EnumCollision(scalar_val));
+    }
+
+    Ok(decrypt_enum(value, key))
 }
 
 #[inline]
-pub fn encrypt_f64(value: f64, key: &[u8]) -> f64 {
+pub fn encrypt_f32(value: f32, key: &[u8]) -> Result<f32, TableEncryptionError> {
     let multiplier = calculate_multiplier(key[0]);
-    ((value * 1000000.0) as i32 * multiplier) as f64
+    let scaled = (value * 10000.0).round();
+
+    if !scaled.is_finite() || scaled < i32::MIN as f32 || scaled >= i32::MAX as f32 {
+        return Err(TableEncryptionError::Overflow);
+    }
+
+    (scaled as i32).checked_mul(multiplier).map(|v| v as f32).ok_or(TableEncryptionError::Overflow)
+}
+
+#[inline]
+pub fn encrypt_f64(value: f64, key: &[u8]) -> Result<f64, TableEncryptionError> {
+    let multiplier = calculate_multiplier(key[0]) as i64;
+    let scaled = (value * 1000000.0).round();
+
+    if !scaled.is_finite() || scaled < i64::MIN as f64 || scaled >= i64::MAX as f64 {
+        return Err(TableEncryptionError::Overflow);
+    }
+
+    (scaled as i64).checked_mul(multiplier).map(|v| v as f64).ok_or(TableEncryptionError::Overflow)
 }
 
 pub fn encrypt_string(value: &str, key: &[u8]) -> String {
@@ -130,6 +196,132 @@ pub fn encrypt_string(value: &str, key: &[u8]) -> String {
     STANDARD.encode(&bytes)
 }
 
+macro_rules! impl_table_scalar {
+    ($($ty:ty => $decrypt:ident, $encrypt:ident),* $(,)?) => {
+        $(
+            impl TableDecrypt for $ty {
+                #[inline]
+                fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+                    *self = $decrypt(*self, key);
+                    Ok(())
+                }
+            }
+
+            impl TableEncrypt for $ty {
+                #[inline]
+                fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+                    *self = $encrypt(*self, key);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_table_float {
+    ($($ty:ty => $decrypt:ident, $encrypt:ident),* $(,)?) => {
+        $(
+            impl TableDecrypt for $ty {
+                #[inline]
+                fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+                    *self = $decrypt(*self, key);
+                    Ok(())
+                }
+            }
+
+            impl TableEncrypt for $ty {
+                #[inline]
+                fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+                    *self = $encrypt(*self, key)?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_table_passthrough {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TableDecrypt for $ty {
+                #[inline]
+                fn decrypt(&mut self, _key: &[u8]) -> Result<(), TableEncryptionError> { Ok(()) }
+            }
+
+            impl TableEncrypt for $ty {
+                #[inline]
+                fn encrypt(&mut self, _key: &[u8]) -> Result<(), TableEncryptionError> { Ok(()) }
+            }
+        )*
+    };
+}
+
+impl_table_scalar! {
+    i32 => decrypt_i32, encrypt_i32,
+    i64 => decrypt_i64, encrypt_i64,
+    u32 => decrypt_u32, encrypt_u32,
+    u64 => decrypt_u64, encrypt_u64
+}
+
+impl_table_float! {
+    f32 => decrypt_f32, encrypt_f32,
+    f64 => decrypt_f64, encrypt_f64
+}
+
+impl_table_passthrough!(bool, i8, u8, i16, u16);
+
+impl TableDecrypt for String {
+    fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        *self = decrypt_string(self, key)?;
+        Ok(())
+    }
+}
+
+impl TableEncrypt for String {
+    fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        *self = encrypt_string(self, key);
+        Ok(())
+    }
+}
+
+impl<T: TableDecrypt> TableDecrypt for Option<T> {
+    fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        match self {
+            Some(value) => value.decrypt(key),
+            None => Ok(())
+        }
+    }
+}
+
+impl<T: TableEncrypt> TableEncrypt for Option<T> {
+    fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        match self {
+            Some(value) => value.encrypt(key),
+            None => Ok(())
+        }
+    }
+}
+
+impl<T: TableDecrypt> TableDecrypt for Vec<T> {
+    fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        self.iter_mut().try_for_each(|value| value.decrypt(key))
+    }
+}
+
+impl<T: TableEncrypt> TableEncrypt for Vec<T> {
+    fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> {
+        self.iter_mut().try_for_each(|value| value.encrypt(key))
+    }
+}
+
+impl<T: TableDecrypt + ?Sized> TableDecrypt for Box<T> {
+    fn decrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> { (**self).decrypt(key) }
+}
+
+impl<T: TableEncrypt + ?Sized> TableEncrypt for Box<T> {
+    fn encrypt(&mut self, key: &[u8]) -> Result<(), TableEncryptionError> { (**self).encrypt(key) }
+}
+
 #[inline]
 fn calculate_multiplier(key_byte: u8) -> i32 {
     let mod_value = key_byte.wrapping_sub(5 * ((key_byte / 5) & 0xFE));
@@ -1,15 +1,39 @@
-const ENCRYPTION_KEY: u32 = 2948064217;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+pub const ENCRYPTION_KEY: u32 = 2948064217;
+
+const BLOCK_SIZE: usize = 256;
+
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 1 << 20;
+
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 1 << 18;
 
 #[inline]
 pub fn encrypt(data: &mut [u8], offset: usize, length: usize) {
+    encrypt_with(data, offset, length, ENCRYPTION_KEY);
+}
+
+#[inline]
+pub fn encrypt_with(data: &mut [u8], offset: usize, length: usize, key: u32) {
     if offset >= data.len() || length == 0 {
         return;
     }
 
-    let end = (offset + length).min(data.len());
-    let key_byte = (ENCRYPTION_KEY & 0xFF) as u8;
+    let end = offset.saturating_add(length).min(data.len());
+    let key_byte = (key & 0xFF) as u8;
+    let data = &mut data[offset..end];
+
+    #[cfg(feature = "rayon")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        data.par_chunks_mut(PARALLEL_CHUNK_SIZE)
+            .for_each(|chunk| chunk.iter_mut().for_each(|byte| *byte ^= key_byte));
+        return;
+    }
 
-    data[offset..end].iter_mut().for_each(|byte| *byte ^= key_byte);
+    data.iter_mut().for_each(|byte| *byte ^= key_byte);
 }
 
 #[inline]
@@ -26,7 +50,56 @@ pub fn xor_exact(value: &[u8], key: &[u8]) -> Vec<u8> {
     value.iter().zip(key.iter()).map(|(a, b)| a ^ b).collect()
 }
 
-#[inline]
 pub fn xor_inplace(data: &mut [u8], key: &[u8]) {
-    data.iter_mut().zip(key.iter().cycle()).for_each(|(d, k)| *d ^= k);
+    if key.is_empty() {
+        return;
+    }
+
+    if data.len() < BLOCK_SIZE {
+        data.iter_mut().zip(key.iter().cycle()).for_each(|(d, k)| *d ^= k);
+        return;
+    }
+
+    let block = expand_key(key);
+
+    #[cfg(feature = "rayon")]
+    if data.len() >= PARALLEL_THRESHOLD {
+        let chunk_size = block.len() * (PARALLEL_CHUNK_SIZE / block.len()).max(1);
+        data.par_chunks_mut(chunk_size).for_each(|chunk| xor_blocks(chunk, &block));
+        return;
+    }
+
+    xor_blocks(data, &block);
+}
+
+#[inline]
+pub(crate) fn xor_slices(data: &mut [u8], key: &[u8]) {
+    let (data_chunks, data_remainder) = data.as_chunks_mut::<8>();
+    let (key_chunks, key_remainder) = key.as_chunks::<8>();
+
+    for (d, k) in data_chunks.iter_mut().zip(key_chunks) {
+        *d = (u64::from_ne_bytes(*d) ^ u64::from_ne_bytes(*k)).to_ne_bytes();
+    }
+    data_remainder.iter_mut().zip(key_remainder).for_each(|(d, k)| *d ^= k);
+}
+
+fn expand_key(key: &[u8]) -> Vec<u8> {
+    let unit = key.len() * 8 / gcd(key.len(), 8);
+    let length = unit * (BLOCK_SIZE / unit).max(1);
+    key.iter().cycle().take(length).copied().collect()
+}
+
+#[inline]
+fn xor_blocks(data: &mut [u8], block: &[u8]) {
+    for chunk in data.chunks_mut(block.len()) {
+        xor_slices(chunk, &block[..chunk.len()]);
+    }
+}
+
+#[inline]
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
 }
@@ -9,7 +9,40 @@ pub enum HashError {
     InvalidPath,
 
     #[error("Expected 0x{expected:08X}, got 0x{actual:08X}")]
-    Mismatch { expected: u32, actual: u32 }
+    Mismatch { expected: u32, actual: u32 },
+
+    #[error("Expected checksum 0x{expected:X}, got 0x{actual:X}")]
+    ChecksumMismatch { expected: u64, actual: u64 },
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Path appears more than once in the batch: {0}")]
+    DuplicatePath(std::path::PathBuf),
+
+    #[error("Match source is forged in the same batch: {0}")]
+    SourceInBatch(std::path::PathBuf),
+
+    #[error(transparent)]
+    Spec(#[from] CrcSpecError)
+}
+
+#[derive(Error, Debug)]
+pub enum CrcSpecError {
+    #[error("CRC width must be between 1 and 64, got {0}")]
+    InvalidWidth(u8),
+
+    #[error("Parameter does not fit in {0} bits")]
+    ParameterTooWide(u8),
+
+    #[error("Polynomial 0x{0:X} has no constant term")]
+    EvenPolynomial(u64),
+
+    #[error("Forging needs a whole number of bytes, width is {0}")]
+    UnsupportedWidth(u8),
+
+    #[error("Offset {offset} is out of range for {len} bytes")]
+    OutOfRange { offset: u64, len: u64 }
 }
 
 #[derive(Error, Debug)]
@@ -21,7 +54,128 @@ pub enum TableEncryptionError {
     FromUtf16Error(#[from] std::string::FromUtf16Error),
 
     #[error("String conversion failed")]
-    StringConversionFailed
+    StringConversionFailed,
+
+    #[error("Value out of range for table encryption")]
+    Overflow,
+
+    #[error("Enum value {0} encrypts to zero")]
+    EnumCollision(i32)
+}
+
+#[derive(Error, Debug)]
+pub enum TableBundleError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
+    #[error("Invalid file path")]
+    InvalidPath,
+
+    #[error("Entry not found: {0}")]
+    EntryNotFound(String),
+
+    #[error("Invalid entry name: {0}")]
+    InvalidEntryName(String)
+}
+
+#[derive(Error, Debug)]
+pub enum TableFileError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid file path")]
+    InvalidPath,
+
+    #[error("Decrypted {0} is not a valid FlatBuffer, wrong key name?")]
+    InvalidFlatBuffer(String)
+}
+
+#[cfg(feature = "sqlite")]
+#[derive(Error, Debug)]
+pub enum ExcelDbError {
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Invalid file path")]
+    InvalidPath,
+
+    #[error("Table not found: {0}")]
+    TableNotFound(String),
+
+    #[error("Table {0} has no Bytes column")]
+    MissingBytesColumn(String)
+}
+
+#[derive(Error, Debug)]
+pub enum AssetBundleError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Not a UnityFS asset bundle")]
+    NotAssetBundle,
+
+    #[error("Truncated asset bundle header")]
+    TruncatedHeader
+}
+
+#[derive(Error, Debug)]
+pub enum MemoryPackError {
+    #[error(transparent)]
+    FromUtf8Error(#[from] std::string::FromUtf8Error),
+
+    #[error(transparent)]
+    FromUtf16Error(#[from] std::string::FromUtf16Error),
+
+    #[error("Unexpected end of MemoryPack data")]
+    UnexpectedEof,
+
+    #[error("Invalid collection length: {0}")]
+    InvalidLength(i32),
+
+    #[error("Length {0} does not fit in a MemoryPack header")]
+    TooLong(usize),
+
+    #[error("Unexpected null {0}")]
+    NullObject(&'static str),
+
+    #[error("Member count {0} is reserved in MemoryPack object headers")]
+    InvalidMemberCount(u8),
+
+    #[error("{name} has {count} members, expected at most {expected}")]
+    UnsupportedMemberCount { name: &'static str, count: u8, expected: u8 }
+}
+
+#[derive(Error, Debug)]
+pub enum CatalogError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    MemoryPack(#[from] MemoryPackError),
+
+    #[error(transparent)]
+    Hash(#[from] HashError),
+
+    #[error("Invalid file path")]
+    InvalidPath
+}
+
+#[derive(Error, Debug)]
+pub enum StrategyError {
+    #[error("Empty file name")]
+    EmptyName,
+
+    #[error("Invalid file name: {0}")]
+    InvalidName(String),
+
+    #[error("Invalid hash: {0}")]
+    InvalidHash(String),
+
+    #[error("Invalid CRC: {0}")]
+    InvalidCrc(String)
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,412 @@
+use crate::error::MemoryPackError;
+
+const NULL_OBJECT: u8 = 255;
+/// Header values above this are reserved by MemoryPack.
+const MAX_MEMBER_COUNT: u8 = 249;
+const NULL_COLLECTION: i32 = -1;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StringEncoding {
+    Utf8,
+    #[default]
+    Utf16
+}
+
+pub trait FromMemoryPack: Sized {
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError>;
+}
+
+pub trait ToMemoryPack {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError>;
+}
+
+#[inline]
+pub fn from_bytes<T: FromMemoryPack>(data: &[u8]) -> Result<T, MemoryPackError> {
+    T::from_memorypack(&mut MemoryPackReader::new(data))
+}
+
+#[inline]
+pub fn to_bytes<T: ToMemoryPack>(value: &T) -> Result<Vec<u8>, MemoryPackError> {
+    let mut writer = MemoryPackWriter::new();
+    value.to_memorypack(&mut writer)?;
+    Ok(writer.into_inner())
+}
+
+pub struct MemoryPackReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    encoding: Option<StringEncoding>
+}
+
+impl<'a> MemoryPackReader<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            encoding: None
+        }
+    }
+
+    #[inline]
+    pub fn position(&self) -> usize { self.position }
+
+    #[inline]
+    pub fn remaining(&self) -> usize { self.data.len() - self.position }
+
+    /// Encoding of the most recently read non-null string.
+    #[inline]
+    pub fn string_encoding(&self) -> Option<StringEncoding> { self.encoding }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], MemoryPackError> {
+        let end = self.position.checked_add(length).ok_or(MemoryPackError::UnexpectedEof)?;
+        let bytes = self.data.get(self.position..end).ok_or(MemoryPackError::UnexpectedEof)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], MemoryPackError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, MemoryPackError> { Ok(self.read_bytes(1)?[0]) }
+
+    #[inline]
+    pub fn read_bool(&mut self) -> Result<bool, MemoryPackError> { Ok(self.read_u8()? != 0) }
+
+    #[inline]
+    pub fn read_i32(&mut self) -> Result<i32, MemoryPackError> {
+        Ok(i32::from_le_bytes(self.read_array()?))
+    }
+
+    #[inline]
+    pub fn read_i64(&mut self) -> Result<i64, MemoryPackError> {
+        Ok(i64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_object_header(&mut self) -> Result<Option<u8>, MemoryPackError> {
+        match self.read_u8()? {
+            NULL_OBJECT => Ok(None),
+            count => Ok(Some(count))
+        }
+    }
+
+    /// Reads an object header with at most `expected` members, `None` if null.
+    ///
+    /// Fewer members are allowed so older payloads keep their defaults.
+    pub fn read_optional_members(
+        &mut self,
+        name: &'static str,
+        expected: u8
+    ) -> Result<Option<u8>, MemoryPackError> {
+        let Some(count) = self.read_object_header()? else { return Ok(None) };
+        if count > expected {
+            return Err(MemoryPackError::UnsupportedMemberCount { name, count, expected });
+        }
+        Ok(Some(count))
+    }
+
+    #[inline]
+    pub fn read_members(
+        &mut self,
+        name: &'static str,
+        expected: u8
+    ) -> Result<u8, MemoryPackError> {
+        self.read_optional_members(name, expected)?.ok_or(MemoryPackError::NullObject(name))
+    }
+
+    pub fn read_collection_header(&mut self) -> Result<Option<usize>, MemoryPackError> {
+        match self.read_i32()? {
+            NULL_COLLECTION => Ok(None),
+            length if length < 0 => Err(MemoryPackError::InvalidLength(length)),
+            length => Ok(Some(length as usize))
+        }
+    }
+
+    pub fn read_string(&mut self) -> Result<Option<String>, MemoryPackError> {
+        let header = self.read_i32()?;
+
+        if header == NULL_COLLECTION {
+            return Ok(None);
+        }
+
+        if header < 0 {
+            let utf8_length = !header as usize;
+            let _utf16_length = self.read_i32()?;
+            let bytes = self.read_bytes(utf8_length)?;
+            self.encoding = Some(StringEncoding::Utf8);
+            return Ok(Some(String::from_utf8(bytes.to_vec())?));
+        }
+
+        let bytes = self.read_bytes(header as usize * 2)?;
+        let units: Vec<u16> =
+            bytes.chunks_exact(2).map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]])).collect();
+        self.encoding = Some(StringEncoding::Utf16);
+        Ok(Some(String::from_utf16(&units)?))
+    }
+
+    #[inline]
+    pub fn read<T: FromMemoryPack>(&mut self) -> Result<T, MemoryPackError> {
+        T::from_memorypack(self)
+    }
+
+    #[inline]
+    pub fn read_vec<T: FromMemoryPack>(&mut self) -> Result<Vec<T>, MemoryPackError> {
+        Ok(self.read_optional_vec()?.unwrap_or_default())
+    }
+
+    pub fn read_optional_vec<T: FromMemoryPack>(
+        &mut self
+    ) -> Result<Option<Vec<T>>, MemoryPackError> {
+        let Some(length) = self.read_collection_header()? else { return Ok(None) };
+
+        let mut items = Vec::with_capacity(length.min(self.remaining()));
+        for _ in 0..length {
+            items.push(T::from_memorypack(self)?);
+        }
+        Ok(Some(items))
+    }
+
+    /// Reads a dictionary as key/value pairs in wire order.
+    #[inline]
+    pub fn read_map<K: FromMemoryPack, V: FromMemoryPack>(
+        &mut self
+    ) -> Result<Vec<(K, V)>, MemoryPackError> {
+        Ok(self.read_optional_map()?.unwrap_or_default())
+    }
+
+    pub fn read_optional_map<K: FromMemoryPack, V: FromMemoryPack>(
+        &mut self
+    ) -> Result<Option<Vec<(K, V)>>, MemoryPackError> {
+        let Some(length) = self.read_collection_header()? else { return Ok(None) };
+
+        let mut entries = Vec::with_capacity(length.min(self.remaining()));
+        for _ in 0..length {
+            let key = K::from_memorypack(self)?;
+            let value = V::from_memorypack(self)?;
+            entries.push((key, value));
+        }
+        Ok(Some(entries))
+    }
+}
+
+#[derive(Default)]
+pub struct MemoryPackWriter {
+    buffer: Vec<u8>,
+    encoding: StringEncoding
+}
+
+impl MemoryPackWriter {
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    #[inline]
+    pub fn with_encoding(encoding: StringEncoding) -> Self {
+        Self {
+            buffer: Vec::new(),
+            encoding
+        }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] { &self.buffer }
+
+    #[inline]
+    pub fn into_inner(self) -> Vec<u8> { self.buffer }
+
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) { self.buffer.extend_from_slice(bytes); }
+
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) { self.buffer.push(value); }
+
+    #[inline]
+    pub fn write_bool(&mut self, value: bool) { self.write_u8(value as u8); }
+
+    #[inline]
+    pub fn write_i32(&mut self, value: i32) { self.write_bytes(&value.to_le_bytes()); }
+
+    #[inline]
+    pub fn write_i64(&mut self, value: i64) { self.write_bytes(&value.to_le_bytes()); }
+
+    pub fn write_object_header(&mut self, count: u8) -> Result<(), MemoryPackError> {
+        if count > MAX_MEMBER_COUNT {
+            return Err(MemoryPackError::InvalidMemberCount(count));
+        }
+        self.write_u8(count);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn write_null_object(&mut self) { self.write_u8(NULL_OBJECT); }
+
+    pub fn write_collection_header(&mut self, length: usize) -> Result<(), MemoryPackError> {
+        self.write_i32(encode_length(length)?);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn write_null_collection(&mut self) { self.write_i32(NULL_COLLECTION); }
+
+    pub fn write_string(&mut self, value: &str) -> Result<(), MemoryPackError> {
+        let utf16_length = encode_length(value.encode_utf16().count())?;
+
+        match self.encoding {
+            StringEncoding::Utf8 => {
+                self.write_i32(!encode_length(value.len())?);
+                self.write_i32(utf16_length);
+                self.write_bytes(value.as_bytes());
+            }
+            StringEncoding::Utf16 => {
+                self.write_i32(utf16_length);
+                for unit in value.encode_utf16() {
+                    self.write_bytes(&unit.to_le_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn write_null_string(&mut self) { self.write_null_collection(); }
+
+    #[inline]
+    pub fn write<T: ToMemoryPack + ?Sized>(&mut self, value: &T) -> Result<(), MemoryPackError> {
+        value.to_memorypack(self)
+    }
+
+    pub fn write_vec<T: ToMemoryPack>(&mut self, items: &[T]) -> Result<(), MemoryPackError> {
+        self.write_collection_header(items.len())?;
+        items.iter().try_for_each(|item| item.to_memorypack(self))
+    }
+
+    pub fn write_map<K: ToMemoryPack, V: ToMemoryPack>(
+        &mut self,
+        entries: &[(K, V)]
+    ) -> Result<(), MemoryPackError> {
+        self.write_collection_header(entries.len())?;
+        entries.iter().try_for_each(|(key, value)| {
+            key.to_memorypack(self)?;
+            value.to_memorypack(self)
+        })
+    }
+}
+
+#[inline]
+fn encode_length(length: usize) -> Result<i32, MemoryPackError> {
+    i32::try_from(length).map_err(|_| MemoryPackError::TooLong(length))
+}
+
+macro_rules! impl_unmanaged {
+    ($($ty:ty),*) => {
+        $(
+            impl FromMemoryPack for $ty {
+                #[inline]
+                fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+                    Ok(<$ty>::from_le_bytes(reader.read_array()?))
+                }
+            }
+
+            impl ToMemoryPack for $ty {
+                #[inline]
+                fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+                    writer.write_bytes(&self.to_le_bytes());
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_unmanaged!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl FromMemoryPack for bool {
+    #[inline]
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        reader.read_bool()
+    }
+}
+
+impl ToMemoryPack for bool {
+    #[inline]
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        writer.write_bool(*self);
+        Ok(())
+    }
+}
+
+/// Null strings decode as empty, use `Option<String>` to keep the distinction.
+impl FromMemoryPack for String {
+    #[inline]
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        Ok(reader.read_string()?.unwrap_or_default())
+    }
+}
+
+impl FromMemoryPack for Option<String> {
+    #[inline]
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        reader.read_string()
+    }
+}
+
+impl ToMemoryPack for str {
+    #[inline]
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        writer.write_string(self)
+    }
+}
+
+impl ToMemoryPack for String {
+    #[inline]
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        writer.write_string(self)
+    }
+}
+
+impl ToMemoryPack for Option<String> {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        match self {
+            Some(value) => writer.write_string(value),
+            None => {
+                writer.write_null_string();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: FromMemoryPack> FromMemoryPack for Vec<T> {
+    #[inline]
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        reader.read_vec()
+    }
+}
+
+impl<T: ToMemoryPack> ToMemoryPack for Vec<T> {
+    #[inline]
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        writer.write_vec(self)
+    }
+}
+
+impl<T: FromMemoryPack> FromMemoryPack for Option<Vec<T>> {
+    #[inline]
+    fn from_memorypack(reader: &mut MemoryPackReader<'_>) -> Result<Self, MemoryPackError> {
+        reader.read_optional_vec()
+    }
+}
+
+impl<T: ToMemoryPack> ToMemoryPack for Option<Vec<T>> {
+    fn to_memorypack(&self, writer: &mut MemoryPackWriter) -> Result<(), MemoryPackError> {
+        match self {
+            Some(items) => writer.write_vec(items),
+            None => {
+                writer.write_null_collection();
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,19 +1,102 @@
+use std::convert::Infallible;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
 
 use crc32fast::Hasher;
 use tokio::fs;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 use crate::error::HashError;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub read: u64,
+    pub total: Option<u64>
+}
+
+/// Async `compute_streaming` with the default buffer size.
+#[inline]
 pub async fn compute(path: &Path) -> Result<u32, HashError> {
-    if !path.exists() {
-        return Ok(0);
+    compute_streaming_async(path, 0x2000, None).await
+}
+
+#[inline]
+pub async fn compute_streaming_async(
+    path: &Path,
+    buffer_size: usize,
+    suffix: Option<&[u8]>
+) -> Result<u32, HashError> {
+    compute_with_progress(path, buffer_size, suffix, None, |_| {}).await
+}
+
+/// Streams the file through a `buffer_size` buffer, calling `progress` after
+/// every read. Stops with `HashError::Cancelled` once `cancel` is set.
+pub async fn compute_with_progress(
+    path: &Path,
+    buffer_size: usize,
+    suffix: Option<&[u8]>,
+    cancel: Option<&AtomicBool>,
+    progress: impl FnMut(Progress)
+) -> Result<u32, HashError> {
+    if !fs::try_exists(path).await? {
+        return Err(HashError::InvalidPath);
     }
 
-    let bytes = fs::read(path).await?;
-    Ok(compute_bytes(&bytes, None))
+    let file = fs::File::open(path).await?;
+    let total = file.metadata().await?.len();
+
+    let mut hasher = hash_reader(file, buffer_size, Some(total), cancel, progress).await?;
+    if let Some(s) = suffix {
+        hasher.update(s);
+    }
+    Ok(hasher.finalize())
+}
+
+#[inline]
+pub async fn compute_reader<R: AsyncRead + Unpin>(
+    reader: R,
+    buffer_size: usize,
+    cancel: Option<&AtomicBool>,
+    progress: impl FnMut(Progress)
+) -> Result<u32, HashError> {
+    Ok(hash_reader(reader, buffer_size, None, cancel, progress).await?.finalize())
+}
+
+pub async fn compare_async(path: &Path, expected_crc: u32) -> Result<(), HashError> {
+    check(expected_crc, compute(path).await?)
+}
+
+async fn hash_reader<R: AsyncRead + Unpin>(
+    mut reader: R,
+    buffer_size: usize,
+    total: Option<u64>,
+    cancel: Option<&AtomicBool>,
+    mut progress: impl FnMut(Progress)
+) -> Result<Hasher, HashError> {
+    let mut hasher = Hasher::new();
+    let mut buffer = vec![0u8; buffer_size.max(4096)];
+    let mut read = 0u64;
+
+    loop {
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err(HashError::Cancelled);
+        }
+
+        let bytes_read = reader.read(&mut buffer).await?;
+        if bytes_read == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..bytes_read]);
+        read += bytes_read as u64;
+        progress(Progress { read, total });
+    }
+
+    Ok(hasher)
 }
 
 #[inline]
@@ -31,43 +114,169 @@ pub fn compute_streaming(
     buffer_size: usize,
     suffix: Option<&[u8]>
 ) -> Result<u32, HashError> {
+    let mut hasher = Hasher::new();
+    read_chunks(path, buffer_size, |chunk| hasher.update(chunk))?;
+
+    if let Some(s) = suffix {
+        hasher.update(s);
+    }
+
+    Ok(hasher.finalize())
+}
+
+#[inline]
+pub fn compare(path: &Path, expected_crc: u32) -> Result<(), HashError> {
+    check(expected_crc, compute_streaming(path, 0x2000, None)?)
+}
+
+/// Feeds the file at `path` to `update` in `buffer_size` chunks, shared by
+/// every streaming CRC.
+pub(crate) fn read_chunks(
+    path: &Path,
+    buffer_size: usize,
+    mut update: impl FnMut(&[u8])
+) -> Result<(), HashError> {
     if !path.exists() {
         return Err(HashError::InvalidPath);
     }
 
-    let buffer_size = buffer_size.max(4096);
     let mut file = File::open(path)?;
-    let mut hasher = Hasher::new();
-    let mut buffer = vec![0u8; buffer_size];
+    let mut buffer = vec![0u8; buffer_size.max(4096)];
 
     loop {
         let bytes_read = file.read(&mut buffer)?;
         if bytes_read == 0 {
             break;
         }
-        hasher.update(&buffer[..bytes_read]);
+        update(&buffer[..bytes_read]);
     }
 
-    if let Some(s) = suffix {
-        hasher.update(s);
-    }
+    Ok(())
+}
 
-    Ok(hasher.finalize())
+#[inline]
+fn check(expected: u32, actual: u32) -> Result<(), HashError> {
+    if expected == actual { Ok(()) } else { Err(HashError::Mismatch { expected, actual }) }
 }
 
-pub fn compare(path: &Path, expected_crc: u32) -> Result<(), HashError> {
+/// CRC of `A || B` from the CRCs of `A` and `B` and the length of `B`.
+#[inline]
+pub fn combine(crc_a: u32, crc_b: u32, len_b: u64) -> u32 {
+    let mut hasher = Hasher::new_with_initial(crc_a);
+    hasher.combine(&Hasher::new_with_initial_len(crc_b, len_b));
+    hasher.finalize()
+}
+
+/// Same result as `compute_streaming`, hashing `chunk_size` pieces of the
+/// file on worker threads and merging them with `combine`.
+#[inline]
+pub fn compute_parallel(path: &Path, chunk_size: usize) -> Result<u32, HashError> {
+    compute_parallel_with_threads(path, chunk_size, default_threads())
+}
+
+pub fn compute_parallel_with_threads(
+    path: &Path,
+    chunk_size: usize,
+    threads: usize
+) -> Result<u32, HashError> {
     if !path.exists() {
         return Err(HashError::InvalidPath);
     }
 
-    let file_crc = compute_streaming(path, 0x2000, None)?;
+    let chunk_size = chunk_size.max(4096) as u64;
+    let len = path.metadata()?.len();
+
+    parallel_chunks(len.div_ceil(chunk_size) as usize, threads, |index| {
+        let offset = index as u64 * chunk_size;
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+
+        let mut chunk = file.take(chunk_size.min(len - offset));
+        let mut hasher = Hasher::new();
+        let mut buffer = vec![0u8; 0x10000];
+        let mut read = 0u64;
 
-    if file_crc == expected_crc {
-        Ok(())
-    } else {
-        Err(HashError::Mismatch {
-            expected: expected_crc,
-            actual: file_crc
-        })
+        loop {
+            let bytes_read = chunk.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..bytes_read]);
+            read += bytes_read as u64;
+        }
+
+        Ok((hasher.finalize(), read))
+    })
+}
+
+/// Parallel CRC of an in-memory buffer, identical to `compute_bytes`.
+pub fn compute_bytes_parallel(buffer: &[u8], chunk_size: usize) -> u32 {
+    let chunk_size = chunk_size.max(4096);
+    let chunks: Vec<&[u8]> = buffer.chunks(chunk_size).collect();
+
+    let Ok(crc) = parallel_chunks::<Infallible>(chunks.len(), default_threads(), |index| {
+        Ok((crc32fast::hash(chunks[index]), chunks[index].len() as u64))
+    });
+    crc
+}
+
+/// Memory-maps the file and hashes it like `compute_bytes_parallel`.
+#[cfg(feature = "mmap")]
+pub fn compute_mmap(path: &Path, chunk_size: usize) -> Result<u32, HashError> {
+    if !path.exists() {
+        return Err(HashError::InvalidPath);
     }
+
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(0);
+    }
+
+    // SAFETY: the map is read-only; concurrent external writes would only
+    // change the computed value.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(compute_bytes_parallel(&map, chunk_size))
+}
+
+#[inline]
+fn default_threads() -> usize { thread::available_parallelism().map_or(1, NonZeroUsize::get) }
+
+fn parallel_chunks<E: Send>(
+    count: usize,
+    threads: usize,
+    hash: impl Fn(usize) -> Result<(u32, u64), E> + Sync
+) -> Result<u32, E> {
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.clamp(1, count.max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    while !failed.load(Ordering::Relaxed) {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= count {
+                            break;
+                        }
+
+                        let result = hash(index);
+                        if result.is_err() {
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                        results.push((index, result));
+                    }
+                    results
+                })
+            })
+            .collect();
+
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().try_fold(0, |crc, (_, result)| {
+        let (chunk_crc, len) = result?;
+        Ok(combine(crc, chunk_crc, len))
+    })
 }
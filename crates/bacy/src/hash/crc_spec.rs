@@ -0,0 +1,318 @@
+use std::ops::Deref;
+use std::path::Path;
+
+use crate::error::{CrcSpecError, HashError};
+use crate::hash::crc;
+
+/// A CRC in the Rocksoft model: width, polynomial without the top bit,
+/// input/output reflection, initial register and final XOR.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CrcSpec {
+    width: u8,
+    poly: u64,
+    init: u64,
+    refin: bool,
+    refout: bool,
+    xorout: u64
+}
+
+impl CrcSpec {
+    pub const CRC32: Self = Self::unchecked(32, 0x04C11DB7, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+    pub const CRC32C: Self = Self::unchecked(32, 0x1EDC6F41, 0xFFFFFFFF, true, true, 0xFFFFFFFF);
+    pub const CRC32_BZIP2: Self =
+        Self::unchecked(32, 0x04C11DB7, 0xFFFFFFFF, false, false, 0xFFFFFFFF);
+    pub const CRC64_ECMA_182: Self = Self::unchecked(64, 0x42F0E1EBA9EA3693, 0, false, false, 0);
+    pub const CRC64_XZ: Self =
+        Self::unchecked(64, 0x42F0E1EBA9EA3693, u64::MAX, true, true, u64::MAX);
+
+    const fn unchecked(
+        width: u8,
+        poly: u64,
+        init: u64,
+        refin: bool,
+        refout: bool,
+        xorout: u64
+    ) -> Self {
+        Self {
+            width,
+            poly,
+            init,
+            refin,
+            refout,
+            xorout
+        }
+    }
+
+    /// Validates the parameters. The polynomial must have a constant term so
+    /// `x` is invertible, which forging relies on.
+    pub fn new(
+        width: u8,
+        poly: u64,
+        init: u64,
+        refin: bool,
+        refout: bool,
+        xorout: u64
+    ) -> Result<Self, CrcSpecError> {
+        if !(1..=64).contains(&width) {
+            return Err(CrcSpecError::InvalidWidth(width));
+        }
+
+        let spec = Self::unchecked(width, poly, init, refin, refout, xorout);
+        if [poly, init, xorout].iter().any(|value| value & !spec.mask() != 0) {
+            return Err(CrcSpecError::ParameterTooWide(width));
+        }
+        if poly & 1 == 0 {
+            return Err(CrcSpecError::EvenPolynomial(poly));
+        }
+
+        Ok(spec)
+    }
+
+    #[inline]
+    pub const fn width(&self) -> u8 { self.width }
+
+    #[inline]
+    pub const fn poly(&self) -> u64 { self.poly }
+
+    #[inline]
+    pub const fn init(&self) -> u64 { self.init }
+
+    #[inline]
+    pub const fn refin(&self) -> bool { self.refin }
+
+    #[inline]
+    pub const fn refout(&self) -> bool { self.refout }
+
+    #[inline]
+    pub const fn xorout(&self) -> u64 { self.xorout }
+
+    #[inline]
+    pub const fn mask(&self) -> u64 { u64::MAX >> (64 - self.width) }
+
+    #[inline]
+    pub fn digest(&self) -> CrcDigest { CrcDigest::new(*self) }
+
+    /// CRC-32 goes through `crc::compute_bytes`.
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        if *self == Self::CRC32 {
+            return crc::compute_bytes(data, None).into();
+        }
+
+        let mut digest = self.digest();
+        digest.update(data);
+        digest.finalize()
+    }
+
+    /// CRC-32 goes through `crc::compute_streaming`.
+    pub fn compute_streaming(&self, path: &Path, buffer_size: usize) -> Result<u64, HashError> {
+        if *self == Self::CRC32 {
+            return crc::compute_streaming(path, buffer_size, None).map(u64::from);
+        }
+
+        let mut digest = self.digest();
+        crc::read_chunks(path, buffer_size, |chunk| digest.update(chunk))?;
+        Ok(digest.finalize())
+    }
+
+    pub fn compare(&self, path: &Path, expected: u64) -> Result<(), HashError> {
+        let actual = self.compute_streaming(path, 0x2000)?;
+        match actual == expected {
+            true => Ok(()),
+            false => Err(HashError::ChecksumMismatch { expected, actual })
+        }
+    }
+
+    /// Number of bytes a forge patch takes.
+    pub fn patch_len(&self) -> Result<usize, CrcSpecError> {
+        match self.width.is_multiple_of(8) {
+            true => Ok(self.width as usize / 8),
+            false => Err(CrcSpecError::UnsupportedWidth(self.width))
+        }
+    }
+
+    /// Bytes that change the CRC by `crc_delta` when XORed into the data with
+    /// `trailing` bytes after them.
+    #[inline]
+    pub fn patch_delta(&self, crc_delta: u64, trailing: u64) -> Result<CrcPatch, CrcSpecError> {
+        self.patch_len()?;
+        Ok(self.patch_bytes(crc_delta, trailing))
+    }
+
+    /// `patch_delta` for a spec already known to have a whole-byte width.
+    fn patch_bytes(&self, crc_delta: u64, trailing: u64) -> CrcPatch {
+        let delta = crc_delta & self.mask();
+        let register = if self.refout { self.reflect(delta) } else { delta };
+
+        let shift = self.pow_mod(self.inverse_x(), self.width as u128 + trailing as u128 * 8);
+        let patch = self.multiply_mod(register as u128, shift) as u64;
+
+        let mut bytes = (patch << (64 - self.width)).to_be_bytes();
+        if self.refin {
+            bytes.iter_mut().for_each(|byte| *byte = byte.reverse_bits());
+        }
+        CrcPatch {
+            bytes,
+            len: self.width as usize / 8
+        }
+    }
+
+    /// Bytes that, appended to data whose CRC is `current`, give it `target`.
+    #[inline]
+    pub fn forge_after(&self, current: u64, target: u64) -> Result<CrcPatch, CrcSpecError> {
+        self.patch_len()?;
+        Ok(self.append_patch(current, target))
+    }
+
+    /// `forge_after` for a spec already known to have a whole-byte width.
+    pub(crate) fn append_patch(&self, current: u64, target: u64) -> CrcPatch {
+        let mut digest = CrcDigest::resume(*self, current);
+        digest.update(&[0; 8][..self.width as usize / 8]);
+        self.patch_bytes(digest.finalize() ^ target, 0)
+    }
+
+    /// Bytes that, appended to `data`, give it `target`.
+    #[inline]
+    pub fn forge_bytes_for(&self, data: &[u8], target: u64) -> Result<CrcPatch, CrcSpecError> {
+        self.forge_after(self.checksum(data), target)
+    }
+
+    /// XORs a patch into `data` at `offset` so it gets `target`.
+    pub fn forge_in_place(
+        &self,
+        data: &mut [u8],
+        offset: usize,
+        target: u64
+    ) -> Result<(), CrcSpecError> {
+        let size = self.patch_len()?;
+        let len = data.len();
+        if offset.checked_add(size).is_none_or(|end| end > len) {
+            return Err(CrcSpecError::OutOfRange {
+                offset: offset as u64,
+                len: len as u64
+            });
+        }
+
+        let patch = self.patch_delta(self.checksum(data) ^ target, (len - offset - size) as u64)?;
+        data[offset..offset + size].iter_mut().zip(&*patch).for_each(|(b, p)| *b ^= p);
+        Ok(())
+    }
+
+    #[inline]
+    fn reflect(&self, value: u64) -> u64 { value.reverse_bits() >> (64 - self.width) }
+
+    #[inline]
+    fn full_poly(&self) -> u128 { (1u128 << self.width) | self.poly as u128 }
+
+    /// `x^-1 mod G`, since `x * (G - 1) / x = G - 1 = 1`.
+    #[inline]
+    fn inverse_x(&self) -> u128 { (self.full_poly() ^ 1) >> 1 }
+
+    fn multiply_mod(&self, mut a: u128, mut b: u128) -> u128 {
+        let full_poly = self.full_poly();
+        let mut result = 0u128;
+
+        while b != 0 {
+            if b & 1 != 0 {
+                result ^= a;
+            }
+
+            b >>= 1;
+            a <<= 1;
+
+            if a >> self.width != 0 {
+                a ^= full_poly;
+            }
+        }
+
+        result
+    }
+
+    fn pow_mod(&self, mut base: u128, mut exponent: u128) -> u128 {
+        let mut result = 1u128;
+
+        while exponent != 0 {
+            if exponent & 1 != 0 {
+                result = self.multiply_mod(result, base);
+            }
+
+            base = self.multiply_mod(base, base);
+            exponent >>= 1;
+        }
+
+        result
+    }
+}
+
+/// Forge bytes for one `CrcSpec`, at most 8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrcPatch {
+    bytes: [u8; 8],
+    len: usize
+}
+
+impl Deref for CrcPatch {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] { &self.bytes[..self.len] }
+}
+
+/// Incremental CRC over a `CrcSpec`, table-driven on a left-aligned register.
+#[derive(Clone)]
+pub struct CrcDigest {
+    spec: CrcSpec,
+    table: Box<[u64; 256]>,
+    register: u64
+}
+
+impl CrcDigest {
+    pub fn new(spec: CrcSpec) -> Self {
+        let shift = 64 - spec.width;
+        let poly = spec.poly << shift;
+
+        let mut table = Box::new([0u64; 256]);
+        for (i, entry) in table.iter_mut().enumerate() {
+            let mut register = (i as u64) << 56;
+            for _ in 0..8 {
+                register = if register >> 63 != 0 { (register << 1) ^ poly } else { register << 1 };
+            }
+            *entry = register;
+        }
+
+        Self {
+            spec,
+            table,
+            register: spec.init << shift
+        }
+    }
+
+    /// Continues from a finished `checksum`, as if the data behind it had
+    /// already been fed in.
+    pub fn resume(spec: CrcSpec, checksum: u64) -> Self {
+        let mut digest = Self::new(spec);
+        let register = (checksum ^ spec.xorout) & spec.mask();
+        let register = if spec.refout { spec.reflect(register) } else { register };
+        digest.register = register << (64 - spec.width);
+        digest
+    }
+
+    #[inline]
+    pub fn spec(&self) -> &CrcSpec { &self.spec }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            let byte = if self.spec.refin { byte.reverse_bits() } else { byte };
+            let index = ((self.register >> 56) as u8 ^ byte) as usize;
+            self.register = (self.register << 8) ^ self.table[index];
+        }
+    }
+
+    pub fn finalize(&self) -> u64 {
+        let register = self.register >> (64 - self.spec.width);
+        let register = if self.spec.refout { self.spec.reflect(register) } else { register };
+        register ^ self.spec.xorout
+    }
+
+    #[inline]
+    pub fn reset(&mut self) { self.register = self.spec.init << (64 - self.spec.width); }
+}
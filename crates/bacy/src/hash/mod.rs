@@ -1,3 +1,5 @@
 pub mod crc;
+pub mod crc_spec;
+pub mod multi;
 pub mod sha;
 pub mod xxhash;
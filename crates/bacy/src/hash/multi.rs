@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crc32fast::Hasher as Crc32;
+use hmac::{Hmac, KeyInit, Mac};
+use md5::{Digest, Md5};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use sha2::Sha256;
+use xxhash_rust::xxh32::Xxh32;
+use xxhash_rust::xxh64::Xxh64;
+
+use crate::error::HashError;
+
+type HmacMd5 = Hmac<Md5>;
+
+/// Digests produced by a `MultiHasher`, `None` for algorithms not selected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Digests {
+    pub crc32: Option<u32>,
+    pub md5: Option<[u8; 16]>,
+    pub hmac_md5: Option<[u8; 16]>,
+    pub sha256: Option<[u8; 32]>,
+    pub xxhash32: Option<u32>,
+    pub xxhash64: Option<u64>
+}
+
+/// Feeds one pass over the input into every selected algorithm.
+#[derive(Clone, Default)]
+pub struct MultiHasher {
+    crc32: Option<Crc32>,
+    md5: Option<Md5>,
+    hmac_md5: Option<HmacMd5>,
+    sha256: Option<Sha256>,
+    xxhash32: Option<Xxh32>,
+    xxhash64: Option<Xxh64>
+}
+
+impl MultiHasher {
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    #[inline]
+    pub fn with_crc32(mut self) -> Self {
+        self.crc32 = Some(Crc32::new());
+        self
+    }
+
+    #[inline]
+    pub fn with_md5(mut self) -> Self {
+        self.md5 = Some(Md5::new());
+        self
+    }
+
+    #[inline]
+    pub fn with_hmac_md5(mut self, key: &[u8]) -> Self {
+        self.hmac_md5 = Some(HmacMd5::new_from_slice(key).expect("HMAC can take key of any size"));
+        self
+    }
+
+    #[inline]
+    pub fn with_sha256(mut self) -> Self {
+        self.sha256 = Some(Sha256::new());
+        self
+    }
+
+    #[inline]
+    pub fn with_xxhash32(mut self) -> Self {
+        self.xxhash32 = Some(Xxh32::new(0));
+        self
+    }
+
+    #[inline]
+    pub fn with_xxhash64(mut self) -> Self {
+        self.xxhash64 = Some(Xxh64::new(0));
+        self
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        if let Some(hasher) = &mut self.crc32 {
+            hasher.update(data);
+        }
+        if let Some(hasher) = &mut self.md5 {
+            Digest::update(hasher, data);
+        }
+        if let Some(mac) = &mut self.hmac_md5 {
+            Mac::update(mac, data);
+        }
+        if let Some(hasher) = &mut self.sha256 {
+            Digest::update(hasher, data);
+        }
+        if let Some(hasher) = &mut self.xxhash32 {
+            hasher.update(data);
+        }
+        if let Some(hasher) = &mut self.xxhash64 {
+            hasher.update(data);
+        }
+    }
+
+    pub fn finalize(self) -> Digests {
+        Digests {
+            crc32: self.crc32.map(Crc32::finalize),
+            md5: self.md5.map(|hasher| hasher.finalize().into()),
+            hmac_md5: self.hmac_md5.map(|mac| mac.finalize().into_bytes().into()),
+            sha256: self.sha256.map(|hasher| hasher.finalize().into()),
+            xxhash32: self.xxhash32.map(|hasher| hasher.digest()),
+            xxhash64: self.xxhash64.map(|hasher| hasher.digest())
+        }
+    }
+
+    pub fn hash_bytes(mut self, data: &[u8]) -> Digests {
+        self.update(data);
+        self.finalize()
+    }
+
+    pub fn hash_reader<R: Read>(mut self, mut reader: R) -> Result<Digests, HashError> {
+        let mut buffer = vec![0u8; 0x10000];
+
+        loop {
+            let bytes_read = reader.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+            self.update(&buffer[..bytes_read]);
+        }
+
+        Ok(self.finalize())
+    }
+
+    pub fn hash_file(self, path: &Path) -> Result<Digests, HashError> {
+        if !path.exists() {
+            return Err(HashError::InvalidPath);
+        }
+
+        self.hash_reader(File::open(path)?)
+    }
+}
@@ -1,5 +1,8 @@
+pub mod catalog;
 pub mod crypto;
 pub mod error;
+pub mod format;
 pub mod hash;
 pub mod math;
+pub mod table;
 pub mod utils;
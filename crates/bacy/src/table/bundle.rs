@@ -0,0 +1,340 @@
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use zip::unstable::write::FileOptionsExt;
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, CompressionMethod, DateTime, ZipArchive, ZipWriter};
+
+use crate::crypto::table::create_password;
+use crate::error::TableBundleError;
+
+const PASSWORD_LENGTH: usize = 20;
+const TABLE_EXTENSION: &str = ".bytes";
+
+pub struct TableEntry {
+    pub name: String,
+    pub data: Vec<u8>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleEncryption {
+    None,
+    ZipCrypto,
+    Aes(AesMode)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EntryLayout {
+    pub compression: CompressionMethod,
+    pub encryption: BundleEncryption,
+    pub last_modified: Option<DateTime>
+}
+
+impl Default for EntryLayout {
+    fn default() -> Self {
+        Self {
+            compression: CompressionMethod::Deflated,
+            encryption: BundleEncryption::ZipCrypto,
+            last_modified: None
+        }
+    }
+}
+
+pub struct TableBundle<R: Read + Seek> {
+    archive: ZipArchive<R>,
+    password: String
+}
+
+#[inline]
+pub fn bundle_password(file_name: &str) -> String {
+    create_password(&file_name.to_lowercase(), PASSWORD_LENGTH)
+}
+
+impl TableBundle<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TableBundleError> {
+        let path = path.as_ref();
+        let file_name =
+            path.file_name().and_then(|n| n.to_str()).ok_or(TableBundleError::InvalidPath)?;
+
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file), file_name)
+    }
+}
+
+impl<R: Read + Seek> TableBundle<R> {
+    pub fn from_reader(reader: R, file_name: &str) -> Result<Self, TableBundleError> {
+        if file_name.is_empty() {
+            return Err(TableBundleError::InvalidPath);
+        }
+
+        Ok(Self {
+            archive: ZipArchive::new(reader)?,
+            password: bundle_password(file_name)
+        })
+    }
+
+    #[inline]
+    pub fn password(&self) -> &str { &self.password }
+
+    /// Number of table entries, matching [`names`](Self::names).
+    #[inline]
+    pub fn len(&self) -> usize { self.table_names().count() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.table_names().next().is_none() }
+
+    pub fn names(&self) -> Vec<String> {
+        self.table_names().map(|name| name.into_owned()).collect()
+    }
+
+    pub fn read(&mut self, name: &str) -> Result<Vec<u8>, TableBundleError> {
+        let index = self
+            .archive
+            .index_for_name(name)
+            .ok_or_else(|| TableBundleError::EntryNotFound(name.to_string()))?;
+
+        Ok(self.read_index(index)?.data)
+    }
+
+    pub fn layout(&mut self) -> Result<Vec<(String, EntryLayout)>, TableBundleError> {
+        let mut layout = Vec::with_capacity(self.archive.len());
+
+        for index in 0..self.archive.len() {
+            let aes = self.archive.get_aes_verification_key_and_salt(index)?;
+            let file = self.archive.by_index_raw(index)?;
+
+            let encryption = match aes {
+                Some(info) => BundleEncryption::Aes(info.aes_mode),
+                None if file.encrypted() => BundleEncryption::ZipCrypto,
+                None => BundleEncryption::None
+            };
+
+            layout.push((file.name()?.into_owned(), EntryLayout {
+                compression: file.compression(),
+                encryption,
+                last_modified: file.last_modified()
+            }));
+        }
+
+        Ok(layout)
+    }
+
+    pub fn entries(&mut self) -> TableEntries<'_, R> { TableEntries { bundle: self, index: 0 } }
+
+    pub fn extract(
+        &mut self,
+        output_dir: impl AsRef<Path>
+    ) -> Result<Vec<PathBuf>, TableBundleError> {
+        let output_dir = output_dir.as_ref();
+        fs::create_dir_all(output_dir)?;
+
+        let mut written = Vec::new();
+        for entry in self.entries() {
+            let entry = entry?;
+            let file_name = Path::new(&entry.name)
+                .file_name()
+                .ok_or_else(|| TableBundleError::InvalidEntryName(entry.name.clone()))?;
+
+            let target = output_dir.join(file_name);
+            fs::write(&target, &entry.data)?;
+            written.push(target);
+        }
+
+        Ok(written)
+    }
+
+    fn table_names(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.archive.file_names().filter_map(Result::ok).filter(|name| is_table_name(name))
+    }
+
+    fn read_index(&mut self, index: usize) -> Result<TableEntry, TableBundleError> {
+        let mut file = self.archive.by_index_decrypt(index, self.password.as_bytes())?;
+        let name = file.name()?.into_owned();
+
+        let mut data = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut data)?;
+
+        Ok(TableEntry { name, data })
+    }
+}
+
+pub struct TableEntries<'a, R: Read + Seek> {
+    bundle: &'a mut TableBundle<R>,
+    index: usize
+}
+
+impl<R: Read + Seek> Iterator for TableEntries<'_, R> {
+    type Item = Result<TableEntry, TableBundleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.bundle.archive.len() {
+            let index = self.index;
+            self.index += 1;
+
+            let is_table = match self.bundle.archive.name_for_index(index) {
+                Some(Ok(name)) => is_table_name(&name),
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => false
+            };
+
+            if is_table {
+                return Some(self.bundle.read_index(index));
+            }
+        }
+
+        None
+    }
+}
+
+pub struct TableBundleWriter {
+    password: String,
+    default_layout: EntryLayout,
+    entries: Vec<WriterEntry>,
+    passthrough: Vec<u8>
+}
+
+enum WriterEntry {
+    Table(TableEntry, EntryLayout),
+    /// Non-table entry of the template, copied as-is from `passthrough`.
+    Raw(usize)
+}
+
+impl TableBundleWriter {
+    pub fn new(file_name: &str) -> Self {
+        Self {
+            password: bundle_password(file_name),
+            default_layout: EntryLayout::default(),
+            entries: Vec::new(),
+            passthrough: Vec::new()
+        }
+    }
+
+    pub fn from_template<R: Read + Seek>(
+        file_name: &str,
+        template: &mut TableBundle<R>
+    ) -> Result<Self, TableBundleError> {
+        let mut writer = Self::new(file_name);
+        let mut passthrough = ZipWriter::new(Cursor::new(Vec::new()));
+        let mut raw_count = 0;
+
+        for (index, (name, layout)) in template.layout()?.into_iter().enumerate() {
+            if !is_table_name(&name) {
+                passthrough.raw_copy_file(template.archive.by_index_raw(index)?)?;
+                writer.entries.push(WriterEntry::Raw(raw_count));
+                raw_count += 1;
+                continue;
+            }
+
+            let data = template.read_index(index)?.data;
+            writer.entries.push(WriterEntry::Table(TableEntry { name, data }, layout));
+        }
+
+        if raw_count > 0 {
+            writer.passthrough = passthrough.finish()?.into_inner();
+        }
+
+        let first_layout = writer.tables().next().map(|(_, layout)| *layout);
+        if let Some(layout) = first_layout {
+            writer.default_layout = EntryLayout {
+                last_modified: None,
+                ..layout
+            };
+        }
+
+        Ok(writer)
+    }
+
+    #[inline]
+    pub fn password(&self) -> &str { &self.password }
+
+    #[inline]
+    pub fn with_layout(mut self, layout: EntryLayout) -> Self {
+        self.default_layout = layout;
+        self
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, data: Vec<u8>) {
+        let name = name.into();
+
+        let existing = self.entries.iter_mut().find_map(|entry| match entry {
+            WriterEntry::Table(entry, _) if entry.name == name => Some(entry),
+            _ => None
+        });
+
+        match existing {
+            Some(entry) => entry.data = data,
+            None => self
+                .entries
+                .push(WriterEntry::Table(TableEntry { name, data }, self.default_layout))
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Vec<u8>> {
+        let index = self.entries.iter().position(
+            |entry| matches!(entry, WriterEntry::Table(entry, _) if entry.name == name)
+        )?;
+        match self.entries.remove(index) {
+            WriterEntry::Table(entry, _) => Some(entry.data),
+            WriterEntry::Raw(_) => None
+        }
+    }
+
+    pub fn write_to<W: Write + Seek>(&self, writer: W) -> Result<W, TableBundleError> {
+        let mut zip = ZipWriter::new(writer);
+        let mut passthrough = (!self.passthrough.is_empty())
+            .then(|| ZipArchive::new(Cursor::new(self.passthrough.as_slice())))
+            .transpose()?;
+
+        for entry in &self.entries {
+            let (entry, layout) = match entry {
+                WriterEntry::Table(entry, layout) => (entry, layout),
+                WriterEntry::Raw(index) => {
+                    if let Some(archive) = passthrough.as_mut() {
+                        zip.raw_copy_file(archive.by_index_raw(*index)?)?;
+                    }
+                    continue;
+                }
+            };
+
+            let mut options = SimpleFileOptions::default().compression_method(layout.compression);
+            if let Some(time) = layout.last_modified {
+                options = options.last_modified_time(time);
+            }
+
+            options = match layout.encryption {
+                BundleEncryption::None => options,
+                BundleEncryption::ZipCrypto => {
+                    options.with_deprecated_encryption(self.password.as_bytes())?
+                }
+                BundleEncryption::Aes(mode) => options.with_aes_encryption(mode, &self.password)
+            };
+
+            zip.start_file(entry.name.as_str(), options)?;
+            zip.write_all(&entry.data)?;
+        }
+
+        Ok(zip.finish()?)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), TableBundleError> {
+        let file = File::create(path)?;
+        self.write_to(BufWriter::new(file))?.flush()?;
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, TableBundleError> {
+        Ok(self.write_to(Cursor::new(Vec::new()))?.into_inner())
+    }
+
+    fn tables(&self) -> impl Iterator<Item = (&TableEntry, &EntryLayout)> {
+        self.entries.iter().filter_map(|entry| match entry {
+            WriterEntry::Table(entry, layout) => Some((entry, layout)),
+            WriterEntry::Raw(_) => None
+        })
+    }
+}
+
+#[inline]
+fn is_table_name(name: &str) -> bool { name.to_lowercase().ends_with(TABLE_EXTENSION) }
@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::crypto::table;
+use crate::error::ExcelDbError;
+
+const SCHEMA_SUFFIX: &str = "DBSchema";
+const BYTES_COLUMN: &str = "Bytes";
+
+pub struct ExcelDbRow {
+    pub row_id: i64,
+    pub bytes: Vec<u8>
+}
+
+pub struct ExcelDb {
+    connection: Connection
+}
+
+pub fn table_key_name(table_name: &str) -> Option<String> {
+    let name = table_name.strip_suffix(SCHEMA_SUFFIX)?;
+    if name.is_empty() {
+        return None;
+    }
+    Some(name.to_lowercase())
+}
+
+/// `CharacterDBSchema` -> `CharacterExcel`.
+pub fn record_key_name(table_name: &str) -> Option<String> {
+    let name = table_name.strip_suffix(SCHEMA_SUFFIX).filter(|name| !name.is_empty())?;
+    Some(format!("{name}Excel"))
+}
+
+impl ExcelDb {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ExcelDbError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(ExcelDbError::InvalidPath);
+        }
+
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Ok(Self { connection })
+    }
+
+    #[inline]
+    pub fn from_connection(connection: Connection) -> Self { Self { connection } }
+
+    pub fn tables(&self) -> Result<Vec<String>, ExcelDbError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")?;
+
+        let names = statement.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut tables = Vec::new();
+        for name in names {
+            let name = name?;
+            if table_key_name(&name).is_some() {
+                tables.push(name);
+            }
+        }
+
+        Ok(tables)
+    }
+
+    /// The key for the row fields, derived from `<Name>Excel` like the
+    /// `KEY_NAME` of the matching table record.
+    pub fn key(&self, table_name: &str) -> Result<[u8; 8], ExcelDbError> {
+        let key_name = record_key_name(table_name)
+            .ok_or_else(|| ExcelDbError::TableNotFound(table_name.to_string()))?;
+        Ok(table::create_key(&key_name))
+    }
+
+    pub fn rows(&self, table_name: &str) -> Result<Vec<ExcelDbRow>, ExcelDbError> {
+        let mut rows = Vec::new();
+        self.for_each_row(table_name, |row| rows.push(row))?;
+        Ok(rows)
+    }
+
+    pub fn for_each_row<F>(&self, table_name: &str, mut callback: F) -> Result<(), ExcelDbError>
+    where
+        F: FnMut(ExcelDbRow)
+    {
+        let key_name = self.schema_key_name(table_name)?;
+
+        let query = format!(
+            "SELECT rowid, {} FROM {} ORDER BY rowid",
+            quote_identifier(BYTES_COLUMN),
+            quote_identifier(table_name)
+        );
+        let mut statement = self.connection.prepare(&query)?;
+        let mut rows = statement.query([])?;
+
+        while let Some(row) = rows.next()? {
+            let mut bytes: Vec<u8> = row.get(1)?;
+            table::xor(&key_name, &mut bytes);
+            callback(ExcelDbRow {
+                row_id: row.get(0)?,
+                bytes
+            });
+        }
+
+        Ok(())
+    }
+
+    fn schema_key_name(&self, table_name: &str) -> Result<String, ExcelDbError> {
+        let not_found = || ExcelDbError::TableNotFound(table_name.to_string());
+        let key_name = table_key_name(table_name).ok_or_else(not_found)?;
+
+        let mut statement =
+            self.connection.prepare_cached("SELECT name FROM pragma_table_info(?1)")?;
+        let columns = statement
+            .query_map([table_name], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if columns.is_empty() {
+            return Err(not_found());
+        }
+        if !columns.iter().any(|column| column == BYTES_COLUMN) {
+            return Err(ExcelDbError::MissingBytesColumn(table_name.to_string()));
+        }
+
+        Ok(key_name)
+    }
+}
+
+fn quote_identifier(name: &str) -> String { format!("\"{}\"", name.replace('"', "\"\"")) }
@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+
+use crate::crypto::table;
+use crate::error::TableFileError;
+
+const MIN_BUFFER_SIZE: usize = 12;
+
+pub fn table_key_name(file_name: &str) -> Option<String> {
+    let stem = Path::new(file_name).file_stem().and_then(|s| s.to_str())?;
+    if stem.is_empty() {
+        return None;
+    }
+    Some(stem.to_lowercase())
+}
+
+pub fn decrypt_table_file(path: impl AsRef<Path>) -> Result<Vec<u8>, TableFileError> {
+    let path = path.as_ref();
+    let file_name = path.file_name().and_then(|n| n.to_str()).ok_or(TableFileError::InvalidPath)?;
+
+    let data = fs::read(path)?;
+    decrypt_table_bytes(file_name, data)
+}
+
+pub fn decrypt_table_bytes(file_name: &str, mut data: Vec<u8>) -> Result<Vec<u8>, TableFileError> {
+    let key_name = table_key_name(file_name).ok_or(TableFileError::InvalidPath)?;
+
+    table::xor(&key_name, &mut data);
+
+    if !is_flatbuffer(&data) {
+        return Err(TableFileError::InvalidFlatBuffer(key_name));
+    }
+
+    Ok(data)
+}
+
+pub fn is_flatbuffer(data: &[u8]) -> bool {
+    if has_valid_root(data) {
+        return true;
+    }
+
+    match read_u32(data, 0) {
+        Some(size) if size as usize == data.len() - 4 => has_valid_root(&data[4..]),
+        _ => false
+    }
+}
+
+fn has_valid_root(data: &[u8]) -> bool {
+    if data.len() < MIN_BUFFER_SIZE {
+        return false;
+    }
+
+    let Some(root) = read_u32(data, 0).map(|v| v as usize) else { return false };
+    if root < 4 || !root.is_multiple_of(4) {
+        return false;
+    }
+
+    let Some(soffset) = read_i32(data, root) else { return false };
+    let vtable = root as i64 - soffset as i64;
+    if vtable < 0 || !(vtable as usize).is_multiple_of(2) {
+        return false;
+    }
+
+    let vtable = vtable as usize;
+    let (Some(vtable_size), Some(object_size)) =
+        (read_u16(data, vtable), read_u16(data, vtable + 2))
+    else {
+        return false;
+    };
+
+    let (vtable_size, object_size) = (vtable_size as usize, object_size as usize);
+    vtable_size >= 4
+        && vtable_size.is_multiple_of(2)
+        && object_size >= 4
+        && vtable + vtable_size <= data.len()
+        && root + object_size <= data.len()
+}
+
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[inline]
+fn read_i32(data: &[u8], offset: usize) -> Option<i32> { read_u32(data, offset).map(|v| v as i32) }
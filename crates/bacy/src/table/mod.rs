@@ -0,0 +1,4 @@
+pub mod bundle;
+#[cfg(feature = "sqlite")]
+pub mod exceldb;
+pub mod file;
@@ -1,84 +1,363 @@
-use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{array, iter, thread};
 
-use crate::error::HashError;
+use crate::error::{CrcSpecError, HashError};
 use crate::hash::crc;
+use crate::hash::crc_spec::{CrcPatch, CrcSpec};
 
-const POLY_NORMAL: u64 = 0x104C11DB7;
-const POLY_DEGREE: i32 = 32;
-const GF2_INVERSE_X32: u64 = 0xCBF1ACDA;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatchMode {
+    Overwrite,
+    Insert
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ForgeTarget {
+    Crc(u32),
+    /// A value of the job's `CrcSpec` width.
+    Checksum(u64),
+    File(PathBuf)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForgeJob {
+    pub path: PathBuf,
+    pub target: ForgeTarget,
+    pub spec: CrcSpec
+}
+
+impl ForgeJob {
+    pub fn crc(path: impl AsRef<Path>, target_crc: u32) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            target: ForgeTarget::Crc(target_crc),
+            spec: CrcSpec::CRC32
+        }
+    }
+
+    pub fn checksum(path: impl AsRef<Path>, spec: CrcSpec, target: u64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            target: ForgeTarget::Checksum(target),
+            spec
+        }
+    }
+
+    pub fn matching(path: impl AsRef<Path>, original: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            target: ForgeTarget::File(original.as_ref().to_path_buf()),
+            spec: CrcSpec::CRC32
+        }
+    }
+
+    #[inline]
+    pub fn with_spec(mut self, spec: CrcSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForgeStatus {
+    AlreadyMatching,
+    Forged
+}
+
+#[derive(Debug)]
+pub struct ForgeReport {
+    pub path: PathBuf,
+    /// Only set for CRC-32 jobs.
+    pub target_crc: Option<u32>,
+    pub target_checksum: Option<u64>,
+    pub result: Result<ForgeStatus, HashError>
+}
+
+/// Forges a file's CRC, CRC-32 unless `with_spec` picks another variant.
+/// The `_crc` methods take CRC-32 values, the `_checksum` ones values of
+/// the spec's width.
 pub struct CrcManipulator {
-    pub file_path: Box<Path>
+    pub file_path: Box<Path>,
+    spec: CrcSpec
 }
 
 impl CrcManipulator {
     pub fn new(file_path: impl AsRef<Path>) -> Self {
         Self {
-            file_path: file_path.as_ref().into()
+            file_path: file_path.as_ref().into(),
+            spec: CrcSpec::CRC32
         }
     }
 
     #[inline]
-    fn gf2_multiply_mod(mut a: u64, mut b: u64) -> u64 {
-        let mut result = 0u64;
+    pub fn with_spec(mut self, spec: CrcSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    #[inline]
+    pub fn spec(&self) -> &CrcSpec { &self.spec }
+
+    #[inline]
+    pub fn forge_crc(&self, target_crc: u32) -> Result<(), HashError> {
+        self.forge_checksum(target_crc.into())
+    }
+
+    pub fn forge_checksum(&self, target: u64) -> Result<(), HashError> {
+        if let Some(patch) = self.plan(target)? {
+            self.append(&patch)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`plan_forge`] for this manipulator's spec.
+    pub fn plan(&self, target: u64) -> Result<Option<CrcPatch>, HashError> {
+        let current = self.spec.compute_streaming(&self.file_path, 0x2000)?;
+        match current == target {
+            true => Ok(None),
+            false => Ok(Some(self.spec.forge_after(current, target)?))
+        }
+    }
+
+    fn append(&self, patch: &[u8]) -> Result<(), HashError> {
+        let mut file = OpenOptions::new().append(true).open(&self.file_path)?;
+        file.write_all(patch)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn forge_crc_at(
+        &self,
+        target_crc: u32,
+        offset: u64,
+        mode: PatchMode
+    ) -> Result<(), HashError> {
+        self.forge_checksum_at(target_crc.into(), offset, mode)
+    }
+
+    /// Forges `target` by patching `CrcSpec::patch_len` bytes at `offset`
+    /// instead of appending, either XORing over existing bytes or inserting
+    /// new ones.
+    pub fn forge_checksum_at(
+        &self,
+        target: u64,
+        offset: u64,
+        mode: PatchMode
+    ) -> Result<(), HashError> {
+        let size = self.spec.patch_len()?;
+        let len = fs::metadata(&self.file_path)?.len();
+
+        match mode {
+            PatchMode::Overwrite => {
+                if offset.checked_add(size as u64).is_none_or(|end| end > len) {
+                    return Err(CrcSpecError::OutOfRange { offset, len }.into());
+                }
 
-        while b != 0 {
-            if b & 1 != 0 {
-                result ^= a;
+                let current = self.spec.compute_streaming(&self.file_path, 0x2000)?;
+                if current == target {
+                    return Ok(());
+                }
+
+                let trailing = len - offset - size as u64;
+                let delta = self.spec.patch_delta(current ^ target, trailing)?;
+                let mut file = OpenOptions::new().read(true).write(true).open(&self.file_path)?;
+                let mut buffer = [0u8; 8];
+                let bytes = &mut buffer[..size];
+                file.seek(SeekFrom::Start(offset))?;
+                file.read_exact(bytes)?;
+
+                bytes.iter_mut().zip(&*delta).for_each(|(b, d)| *b ^= d);
+                file.seek(SeekFrom::Start(offset))?;
+                file.write_all(bytes)?;
             }
+            PatchMode::Insert => {
+                if offset > len {
+                    return Err(CrcSpecError::OutOfRange { offset, len }.into());
+                }
 
-            b >>= 1;
-            a <<= 1;
+                let start = offset as usize;
+                let mut data = fs::read(&self.file_path)?;
+                data.splice(start..start, iter::repeat_n(0, size));
 
-            if a >> POLY_DEGREE != 0 {
-                a ^= POLY_NORMAL;
+                let padded = self.spec.checksum(&data);
+                let patch = self.spec.patch_delta(padded ^ target, len - offset)?;
+                data[start..start + size].copy_from_slice(&patch);
+                fs::write(&self.file_path, data)?;
             }
         }
 
-        result
+        Ok(())
     }
 
     #[inline]
-    fn reverse_bits(bytes: &[u8; 4]) -> [u8; 4] {
-        u32::from_be_bytes(*bytes).reverse_bits().to_be_bytes()
+    pub fn forge_verified(&self, target_crc: u32) -> Result<ForgeStatus, HashError> {
+        self.forge_checksum_verified(target_crc.into())
     }
 
-    fn forge_bytes(padded_crc: u32, target_crc: u32) -> [u8; 4] {
-        let xor_result = target_crc ^ padded_crc;
-        let xor_bytes = xor_result.to_be_bytes();
+    /// Forges `target` and checks the result with `CrcSpec::compare`. On
+    /// failure the file is truncated back to its original length.
+    pub fn forge_checksum_verified(&self, target: u64) -> Result<ForgeStatus, HashError> {
+        let original_len = fs::metadata(&self.file_path)?.len();
+        let Some(patch) = self.plan(target)? else {
+            return Ok(ForgeStatus::AlreadyMatching);
+        };
 
-        let reversed_bytes = Self::reverse_bits(&xor_bytes);
+        let result = self.append(&patch).and_then(|_| self.spec.compare(&self.file_path, target));
 
-        let k = u32::from_be_bytes(reversed_bytes) as u64;
-
-        let p_value = Self::gf2_multiply_mod(k, GF2_INVERSE_X32);
-        let p_bytes = (p_value as u32).to_be_bytes();
+        match result {
+            Ok(()) => Ok(ForgeStatus::Forged),
+            Err(e) => {
+                OpenOptions::new().write(true).open(&self.file_path)?.set_len(original_len)?;
+                Err(e)
+            }
+        }
+    }
 
-        p_bytes.map(|b| b.reverse_bits())
+    pub fn match_file(&self, target_file: &Path) -> Result<(), HashError> {
+        let target = self.spec.compute_streaming(target_file, 0x2000)?;
+        self.forge_checksum(target)
     }
+}
 
-    pub fn forge_crc(&self, target_crc: u32) -> Result<(), HashError> {
-        let current_crc = crc::compute_streaming(&self.file_path, 0x2000, None)?;
+/// CRC-32 shortcut for `CrcSpec::forge_bytes_for`.
+#[inline]
+pub fn forge_bytes_for(data: &[u8], target_crc: u32) -> [u8; 4] {
+    forge_after(crc::compute_bytes(data, None), target_crc)
+}
+
+/// Appends the bytes from `forge_bytes_for` to `data`.
+pub fn extend_with_forge(data: &mut Vec<u8>, target_crc: u32) {
+    let patch = forge_bytes_for(data, target_crc);
+    data.extend_from_slice(&patch);
+}
+
+/// Computes the bytes `CrcManipulator::forge_crc` would append without
+/// touching the file, `None` if it already has `target_crc`.
+pub fn plan_forge(path: impl AsRef<Path>, target_crc: u32) -> Result<Option<[u8; 4]>, HashError> {
+    let current_crc = crc::compute_streaming(path.as_ref(), 0x2000, None)?;
+    Ok((current_crc != target_crc).then(|| forge_after(current_crc, target_crc)))
+}
 
-        if current_crc == target_crc {
-            return Ok(());
+#[inline]
+fn forge_after(current_crc: u32, target_crc: u32) -> [u8; 4] {
+    let patch = CrcSpec::CRC32.append_patch(current_crc.into(), target_crc.into());
+    array::from_fn(|i| patch[i])
+}
+
+/// Forges every job on a pool of worker threads, one report per job in input
+/// order. Jobs must not share a path or match another job's file.
+#[inline]
+pub fn forge_batch(jobs: &[ForgeJob]) -> Vec<ForgeReport> {
+    forge_batch_with_threads(jobs, thread::available_parallelism().map_or(1, NonZeroUsize::get))
+}
+
+/// Jobs that share a path (after canonicalization) are all reported as
+/// [`HashError::DuplicatePath`], and jobs matching a file another job forges
+/// as [`HashError::SourceInBatch`]. Either leaves the file untouched.
+pub fn forge_batch_with_threads(jobs: &[ForgeJob], threads: usize) -> Vec<ForgeReport> {
+    let next = AtomicUsize::new(0);
+    let conflicts = conflicting_jobs(jobs);
+
+    let mut reports: Vec<(usize, ForgeReport)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.clamp(1, jobs.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut reports = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(index) else { break };
+                        let report = match &conflicts[index] {
+                            Some(conflict) => ForgeReport {
+                                path: job.path.clone(),
+                                target_crc: None,
+                                target_checksum: None,
+                                result: Err(conflict.to_error(job))
+                            },
+                            None => forge_job(job)
+                        };
+                        reports.push((index, report));
+                    }
+                    reports
+                })
+            })
+            .collect();
+
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+enum Conflict {
+    /// Another job forges the same file.
+    Duplicate,
+    /// The job matches a file another job forges.
+    Source
+}
+
+impl Conflict {
+    fn to_error(&self, job: &ForgeJob) -> HashError {
+        match (self, &job.target) {
+            (Self::Source, ForgeTarget::File(source)) => HashError::SourceInBatch(source.clone()),
+            _ => HashError::DuplicatePath(job.path.clone())
         }
+    }
+}
 
-        let padded_crc = crc::compute_streaming(&self.file_path, 0x2000, Some(&[0, 0, 0, 0]))?;
-        let patch = Self::forge_bytes(padded_crc, target_crc);
+fn conflicting_jobs(jobs: &[ForgeJob]) -> Vec<Option<Conflict>> {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let keys: Vec<_> = jobs.iter().map(|job| canonical(&job.path)).collect();
 
-        let mut file = OpenOptions::new().append(true).open(&self.file_path)?;
-        file.write_all(&patch)?;
-        Ok(())
+    let mut counts = HashMap::with_capacity(keys.len());
+    for key in &keys {
+        *counts.entry(key).or_insert(0usize) += 1;
     }
 
-    pub fn match_file(&self, target_file: &Path) -> Result<(), HashError> {
-        if !target_file.exists() {
-            return Err(HashError::InvalidPath);
+    jobs.iter()
+        .zip(&keys)
+        .map(|(job, key)| {
+            if counts[key] > 1 {
+                return Some(Conflict::Duplicate);
+            }
+            match &job.target {
+                ForgeTarget::File(source) => {
+                    let source_key = canonical(source);
+                    (source_key != *key && counts.contains_key(&source_key))
+                        .then_some(Conflict::Source)
+                }
+                _ => None
+            }
+        })
+        .collect()
+}
+
+fn forge_job(job: &ForgeJob) -> ForgeReport {
+    let target = match &job.target {
+        ForgeTarget::Crc(crc) => Ok(u64::from(*crc)),
+        ForgeTarget::Checksum(checksum) => Ok(*checksum),
+        ForgeTarget::File(original) => job.spec.compute_streaming(original, 0x2000)
+    };
+
+    match target {
+        Ok(target) => ForgeReport {
+            path: job.path.clone(),
+            target_crc: u32::try_from(target).ok().filter(|_| job.spec == CrcSpec::CRC32),
+            target_checksum: Some(target),
+            result: CrcManipulator::new(&job.path)
+                .with_spec(job.spec)
+                .forge_checksum_verified(target)
+        },
+        Err(e) => ForgeReport {
+            path: job.path.clone(),
+            target_crc: None,
+            target_checksum: None,
+            result: Err(e)
         }
-        let target_crc = crc::compute_streaming(target_file, 0x2000, None)?;
-        self.forge_crc(target_crc)
     }
 }
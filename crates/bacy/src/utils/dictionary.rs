@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use crate::catalog::media::MediaCatalog;
+use crate::catalog::table::TableCatalog;
+use crate::hash::xxhash;
+use crate::utils::strategy::HashedFileName;
+
+/// Maps hashed file names produced by `get_file_path` back to their
+/// original names.
+#[derive(Clone, Debug, Default)]
+pub struct NameDictionary {
+    names: HashMap<u64, String>
+}
+
+impl NameDictionary {
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    #[inline]
+    pub fn len(&self) -> usize { self.names.len() }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.names.is_empty() }
+
+    /// Indexes the file name component of `name` by the hash of its raw and
+    /// lowercased forms. The first name inserted for a hash wins.
+    pub fn insert(&mut self, name: &str) {
+        let name = file_name(name);
+        if name.is_empty() {
+            return;
+        }
+
+        let lower = name.to_lowercase();
+        for hash in [xxhash::calculate_hash64_str(name), xxhash::calculate_hash64_str(&lower)] {
+            self.names.entry(hash).or_insert_with(|| name.to_string());
+        }
+    }
+
+    pub fn insert_tables(&mut self, catalog: &TableCatalog) {
+        for (name, bundle) in catalog.iter() {
+            self.insert(name);
+            bundle.includes().iter().for_each(|table| self.insert(table));
+        }
+    }
+
+    pub fn insert_media(&mut self, catalog: &MediaCatalog) {
+        catalog.entries().for_each(|media| self.insert(media.path()));
+    }
+
+    /// Inserts one name per non-empty line, returning how many were read.
+    pub fn insert_wordlist(&mut self, reader: impl BufRead) -> io::Result<usize> {
+        let mut count = 0;
+        for line in reader.lines() {
+            let line = line?;
+            let name = line.trim();
+            if !name.is_empty() {
+                self.insert(name);
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    #[inline]
+    pub fn get(&self, hash: u64) -> Option<&str> { self.names.get(&hash).map(String::as_str) }
+
+    /// Resolves a `<hash>` or `<hash>_<crc>` file name to the original name
+    /// and CRC, if present.
+    pub fn lookup(&self, hashed: &str) -> Option<(&str, Option<i64>)> {
+        let hashed = HashedFileName::parse(file_name(hashed)).ok()?;
+        self.get(hashed.hash()?).map(|name| (name, hashed.crc()))
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for NameDictionary {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|name| self.insert(name.as_ref()));
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for NameDictionary {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut dictionary = Self::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+#[inline]
+fn file_name(path: &str) -> &str {
+    Path::new(path.rsplit('\\').next().unwrap_or(path))
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("")
+}
@@ -1,2 +1,3 @@
 pub mod crc_manipulator;
+pub mod dictionary;
 pub mod strategy;
@@ -1,5 +1,8 @@
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use crate::error::StrategyError;
 use crate::hash::xxhash;
 
 pub fn get_file_path<P: AsRef<Path>>(
@@ -9,21 +12,160 @@ pub fn get_file_path<P: AsRef<Path>>(
     to_lower: bool
 ) -> PathBuf {
     let path = path.as_ref();
-    let parent = path.parent();
-    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-    if file_name.is_empty() {
+    let Some(file_name) = file_name(path) else {
         return PathBuf::new();
+    };
+
+    let result = HashedFileName::build(file_name, crc, no_hash, to_lower).to_string();
+    if let Some(parent_path) = path.parent() {
+        parent_path.join(result)
+    } else {
+        PathBuf::from(result)
     }
+}
+
+/// How catalog names map to file names on disk, see `get_file_path`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileNaming {
+    pub crc_suffix: bool,
+    pub no_hash: bool,
+    pub to_lower: bool
+}
 
-    let hash_input = if to_lower { file_name.to_lowercase() } else { file_name.to_string() };
-    let hash = xxhash::calculate_hash64_str(&hash_input);
+impl Default for FileNaming {
+    fn default() -> Self {
+        Self {
+            crc_suffix: true,
+            no_hash: false,
+            to_lower: true
+        }
+    }
+}
 
-    let result = match (crc, no_hash) {
-        (Some(crc_value), _) => format!("{}_{}", hash, crc_value),
-        (None, true) => file_name.to_string(),
-        (None, false) => hash.to_string()
+impl FileNaming {
+    /// Files stored under their catalog name.
+    pub const PLAIN: Self = Self {
+        crc_suffix: false,
+        no_hash: true,
+        to_lower: false
     };
 
-    if let Some(parent_path) = parent { parent_path.join(result) } else { PathBuf::from(result) }
+    pub fn path_for(&self, name: &str, crc: i64) -> PathBuf {
+        let name = name.replace('\\', "/");
+        get_file_path(name, self.crc_suffix.then_some(crc), self.no_hash, self.to_lower)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HashedFileName {
+    Hash(u64),
+    HashWithCrc { hash: u64, crc: i64 },
+    Plain(String)
+}
+
+impl HashedFileName {
+    /// Builds the file name `get_file_path` would produce for `path`, failing
+    /// instead of returning an empty name.
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        crc: Option<i64>,
+        no_hash: bool,
+        to_lower: bool
+    ) -> Result<Self, StrategyError> {
+        let file_name = file_name(path.as_ref()).ok_or(StrategyError::EmptyName)?;
+        if let Some(crc) = crc {
+            validate_crc(crc)?;
+        }
+        Ok(Self::build(file_name, crc, no_hash, to_lower))
+    }
+
+    fn build(file_name: &str, crc: Option<i64>, no_hash: bool, to_lower: bool) -> Self {
+        let hash_input = if to_lower { file_name.to_lowercase() } else { file_name.to_string() };
+        let hash = xxhash::calculate_hash64_str(&hash_input);
+
+        match (crc, no_hash) {
+            (Some(crc), _) => Self::HashWithCrc { hash, crc },
+            (None, true) => Self::Plain(file_name.to_string()),
+            (None, false) => Self::Hash(hash)
+        }
+    }
+
+    /// Classifies a file name as `hash`, `hash_crc` or plain.
+    pub fn parse(name: &str) -> Result<Self, StrategyError> {
+        if name.is_empty() {
+            return Err(StrategyError::EmptyName);
+        }
+        if name.contains(['/', '\\']) {
+            return Err(StrategyError::InvalidName(name.to_string()));
+        }
+
+        let (hash, crc) = match name.split_once('_') {
+            Some((hash, crc)) if is_digits(hash) && is_digits(crc) => (hash, Some(crc)),
+            None if is_digits(name) => (name, None),
+            _ => return Ok(Self::Plain(name.to_string()))
+        };
+
+        let hash = hash.parse().map_err(|_| StrategyError::InvalidHash(hash.to_string()))?;
+        match crc {
+            Some(crc) => {
+                let crc = crc.parse().map_err(|_| StrategyError::InvalidCrc(crc.to_string()))?;
+                validate_crc(crc)?;
+                Ok(Self::HashWithCrc { hash, crc })
+            }
+            None => Ok(Self::Hash(hash))
+        }
+    }
+
+    #[inline]
+    pub fn hash(&self) -> Option<u64> {
+        match self {
+            Self::Hash(hash) | Self::HashWithCrc { hash, .. } => Some(*hash),
+            Self::Plain(_) => None
+        }
+    }
+
+    #[inline]
+    pub fn crc(&self) -> Option<i64> {
+        match self {
+            Self::HashWithCrc { crc, .. } => Some(*crc),
+            _ => None
+        }
+    }
+
+    #[inline]
+    pub fn is_hashed(&self) -> bool { !matches!(self, Self::Plain(_)) }
+}
+
+impl fmt::Display for HashedFileName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hash(hash) => write!(f, "{}", hash),
+            Self::HashWithCrc { hash, crc } => write!(f, "{}_{}", hash, crc),
+            Self::Plain(name) => f.write_str(name)
+        }
+    }
+}
+
+impl FromStr for HashedFileName {
+    type Err = StrategyError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::parse(s) }
+}
+
+#[inline]
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|n| n.to_str()).filter(|n| !n.is_empty())
+}
+
+#[inline]
+fn is_digits(s: &str) -> bool { !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) }
+
+#[inline]
+fn validate_crc(crc: i64) -> Result<(), StrategyError> {
+    if (0..=u32::MAX as i64).contains(&crc) {
+        Ok(())
+    } else {
+        Err(StrategyError::InvalidCrc(crc.to_string()))
+    }
 }
@@ -0,0 +1,160 @@
+use std::io::{self, Read, Write};
+
+use bacy::crypto::assetbundle::*;
+use bacy::crypto::xor::ENCRYPTION_KEY;
+use bacy::error::AssetBundleError;
+
+/// Signature, format version, two version strings and the 20-byte tail.
+const HEADER_LEN: usize = 8 + 4 + 6 + 11 + 20;
+
+fn plain() -> Vec<u8> {
+    let mut data = b"UnityFS\0".to_vec();
+    data.extend_from_slice(&8u32.to_be_bytes());
+    data.extend_from_slice(b"5.x.x\0");
+    data.extend_from_slice(b"2021.3.0f1\0");
+    data.extend((0..20).map(|i| i as u8 + 1));
+    data.extend((0..2000).map(|i| (i % 251) as u8));
+    data
+}
+
+fn encrypted() -> Vec<u8> {
+    let key_byte = ENCRYPTION_KEY as u8;
+    let mut data = plain();
+    data[..HEADER_LEN].iter_mut().for_each(|byte| *byte ^= key_byte);
+    data
+}
+
+#[test]
+fn detects_headers() {
+    let cipher = AssetBundleCipher::default();
+    assert_eq!(cipher.key(), ENCRYPTION_KEY);
+    assert_eq!(cipher.detect(&plain()), BundleState::Plain);
+    assert_eq!(cipher.detect(&encrypted()), BundleState::Encrypted);
+    assert_eq!(cipher.detect(b"UnityFS"), BundleState::Unknown);
+    assert_eq!(cipher.detect(b"UnityWeb\0"), BundleState::Unknown);
+    assert_eq!(
+        AssetBundleCipher::new(ENCRYPTION_KEY ^ 1).detect(&encrypted()),
+        BundleState::Unknown
+    );
+
+    assert_eq!(cipher.obfuscated_range(&plain()).unwrap(), 0..HEADER_LEN);
+    assert_eq!(cipher.obfuscated_range(&encrypted()).unwrap(), 0..HEADER_LEN);
+    assert!(matches!(
+        cipher.obfuscated_range(&plain()[..HEADER_LEN - 1]),
+        Err(AssetBundleError::TruncatedHeader)
+    ));
+    assert!(matches!(
+        cipher.obfuscated_range(&plain()[..20]),
+        Err(AssetBundleError::TruncatedHeader)
+    ));
+    assert!(matches!(
+        cipher.obfuscated_range(b"not a bundle"),
+        Err(AssetBundleError::NotAssetBundle)
+    ));
+
+    let mut unterminated = b"UnityFS\0".to_vec();
+    unterminated.resize(2048, b'x');
+    assert!(matches!(
+        cipher.obfuscated_range(&unterminated),
+        Err(AssetBundleError::NotAssetBundle)
+    ));
+}
+
+#[test]
+fn toggles_in_place() {
+    let cipher = AssetBundleCipher::default();
+
+    let mut data = encrypted();
+    assert_eq!(cipher.decrypt(&mut data).unwrap(), BundleState::Encrypted);
+    assert_eq!(data, plain());
+    assert_eq!(cipher.decrypt(&mut data).unwrap(), BundleState::Plain);
+    assert_eq!(data, plain());
+
+    assert_eq!(cipher.encrypt(&mut data).unwrap(), BundleState::Plain);
+    assert_eq!(data, encrypted());
+    assert_eq!(cipher.encrypt(&mut data).unwrap(), BundleState::Encrypted);
+    assert_eq!(data, encrypted());
+
+    let mut other = b"not a bundle".to_vec();
+    assert!(matches!(cipher.decrypt(&mut other), Err(AssetBundleError::NotAssetBundle)));
+    assert_eq!(other, b"not a bundle");
+}
+
+#[test]
+fn reader_decrypts() {
+    let cipher = AssetBundleCipher::default();
+
+    for (input, state) in [(encrypted(), BundleState::Encrypted), (plain(), BundleState::Plain)] {
+        let mut reader = cipher.reader(&input[..]).unwrap();
+        assert_eq!(reader.state(), state);
+
+        let mut output = Vec::new();
+        let mut chunk = [0u8; 100];
+        loop {
+            match reader.read(&mut chunk).unwrap() {
+                0 => break,
+                n => output.extend_from_slice(&chunk[..n])
+            }
+        }
+        assert_eq!(output, plain());
+    }
+
+    assert!(matches!(cipher.reader(&b"not a bundle"[..]), Err(AssetBundleError::NotAssetBundle)));
+    assert!(matches!(
+        cipher.reader(&encrypted()[..HEADER_LEN - 1]),
+        Err(AssetBundleError::TruncatedHeader)
+    ));
+}
+
+#[test]
+fn writer_toggles() {
+    let cipher = AssetBundleCipher::default();
+
+    for (input, output) in [(encrypted(), plain()), (plain(), encrypted())] {
+        let mut writer = cipher.writer(Vec::new());
+        for chunk in input.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), output);
+
+        let mut writer = cipher.writer(Vec::new());
+        writer.write_all(&input).unwrap();
+        assert_eq!(writer.finish().unwrap(), output);
+    }
+}
+
+#[test]
+fn writer_rejects_bad_input_early() {
+    let cipher = AssetBundleCipher::default();
+
+    let mut writer = cipher.writer(Vec::new());
+    writer.write_all(b"Uni").unwrap();
+    let error = writer.write(b"ty!").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    writer.write_all(b"tyFS\0").unwrap();
+
+    let mut writer = cipher.writer(Vec::new());
+    assert_eq!(writer.write(b"x").unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    // Only the header is buffered, the rest of a large write is left for later
+    // calls.
+    let mut writer = cipher.writer(Vec::new());
+    let written = writer.write(&plain()).unwrap();
+    assert!(written < plain().len());
+    writer.write_all(&plain()[written..]).unwrap();
+    assert_eq!(writer.finish().unwrap(), encrypted());
+
+    let mut unterminated = b"UnityFS\0".to_vec();
+    unterminated.resize(4096, b'x');
+    let mut writer = cipher.writer(Vec::new());
+    assert_eq!(writer.write(&unterminated).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut writer = cipher.writer(Vec::new());
+    writer.write_all(&plain()[..HEADER_LEN - 1]).unwrap();
+    let error = writer.finish().unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(matches!(
+        error.into_inner().unwrap().downcast::<AssetBundleError>().as_deref(),
+        Ok(AssetBundleError::TruncatedHeader)
+    ));
+}
@@ -0,0 +1,121 @@
+use bacy::catalog::diff::*;
+use bacy::catalog::media::MediaCatalog;
+use bacy::catalog::table::TableCatalog;
+use common::catalogs::*;
+
+mod common;
+
+fn state(size: i64, crc: i64) -> EntryState { EntryState { size, crc } }
+
+fn sample() -> CatalogDiff {
+    let old = [("a.zip", state(1, 10)), ("b.zip", state(2, 20)), ("c.zip", state(3, 30))];
+    let new = [("d.zip", state(4, 40)), ("c.zip", state(3, 31)), ("a.zip", state(1, 10))];
+    CatalogDiff::between(old, new)
+}
+
+#[test]
+fn classifies_entries() {
+    let diff = sample();
+
+    assert_eq!(diff.added, [DiffEntry {
+        name: "d.zip".into(),
+        state: state(4, 40)
+    }]);
+    assert_eq!(diff.removed, [DiffEntry {
+        name: "b.zip".into(),
+        state: state(2, 20)
+    }]);
+    assert_eq!(diff.changed, [ChangedEntry {
+        name: "c.zip".into(),
+        old: state(3, 30),
+        new: state(3, 31)
+    }]);
+    assert_eq!(diff.len(), 3);
+}
+
+#[test]
+fn identical_catalogs_are_empty() {
+    let entries = [("a.zip", state(1, 10))];
+    assert!(CatalogDiff::between(entries, entries).is_empty());
+}
+
+/// The first fixtures against the ones with null members, with `A.zip`
+/// changed in the newer tables.
+fn parsed() -> ChangeSet {
+    let old_tables = TableCatalog::from_bytes(TABLE_CATALOG).unwrap();
+    let mut new_tables = TableCatalog::from_bytes(NULL_MEMBERS_CATALOG).unwrap();
+    assert!(new_tables.update("A.zip", 0x11, 0x99));
+
+    let old_media = MediaCatalog::from_bytes(MEDIA_CATALOG).unwrap();
+    let new_media = MediaCatalog::from_bytes(NULL_MEMBERS_MEDIA).unwrap();
+    ChangeSet::between(&old_tables, &new_tables, &old_media, &new_media)
+}
+
+#[test]
+fn diffs_parsed_catalogs() {
+    let changes = parsed();
+
+    assert_eq!(changes.tables.added, [DiffEntry {
+        name: "B.zip".into(),
+        state: state(0x20, 0)
+    }]);
+    assert!(changes.tables.removed.is_empty());
+    assert_eq!(changes.tables.changed, [ChangedEntry {
+        name: "A.zip".into(),
+        old: state(0x10, 0x12345678),
+        new: state(0x11, 0x99)
+    }]);
+
+    assert_eq!(changes.media.added, [DiffEntry {
+        name: "a".into(),
+        state: state(0, 0)
+    }]);
+    assert_eq!(changes.media.removed, [DiffEntry {
+        name: "a/b.ogg".into(),
+        state: state(0x20, 0x2A)
+    }]);
+    assert!(changes.media.changed.is_empty());
+    assert!(!changes.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_parsed_changes() {
+    let json = serde_json::to_value(parsed()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "tables": {
+                "added": [{ "name": "B.zip", "size": 32, "crc": 0 }],
+                "removed": [],
+                "changed": [{
+                    "name": "A.zip",
+                    "old": { "size": 16, "crc": 0x12345678 },
+                    "new": { "size": 17, "crc": 0x99 }
+                }]
+            },
+            "media": {
+                "added": [{ "name": "a", "size": 0, "crc": 0 }],
+                "removed": [{ "name": "a/b.ogg", "size": 32, "crc": 42 }],
+                "changed": []
+            }
+        })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_to_json() {
+    let json = serde_json::to_value(ChangeSet {
+        tables: sample(),
+        media: CatalogDiff::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        json["tables"]["added"][0],
+        serde_json::json!({ "name": "d.zip", "size": 4, "crc": 40 })
+    );
+    assert_eq!(json["tables"]["changed"][0]["new"]["crc"], 31);
+    assert_eq!(json["media"]["removed"], serde_json::json!([]));
+}
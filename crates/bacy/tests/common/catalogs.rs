@@ -0,0 +1,80 @@
+//! Serialized catalogs shared by the MemoryPack and diff tests.
+
+#[rustfmt::skip]
+pub const TABLE_CATALOG: &[u8] = &[
+    // TableCatalog, 2 members
+    0x02,
+    // Table: 1 entry
+    0x01, 0x00, 0x00, 0x00,
+    // key "A.zip" (UTF-8)
+    0xFA, 0xFF, 0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, b'A', b'.', b'z', b'i', b'p',
+    // TableBundle, 8 members
+    0x08,
+    0xFA, 0xFF, 0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, b'A', b'.', b'z', b'i', b'p',
+    0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00,
+    // Includes: ["X.bytes"]
+    0x01, 0x00, 0x00, 0x00,
+    0xF8, 0xFF, 0xFF, 0xFF, 0x07, 0x00, 0x00, 0x00, b'X', b'.', b'b', b'y', b't', b'e', b's',
+    // PatchPacks: empty
+    0x00, 0x00, 0x00, 0x00
+];
+
+#[rustfmt::skip]
+pub const MEDIA_CATALOG: &[u8] = &[
+    // MediaCatalog, 1 member
+    0x01,
+    // Table: 1 entry
+    0x01, 0x00, 0x00, 0x00,
+    // key "a/b.ogg" (UTF-16)
+    0x07, 0x00, 0x00, 0x00,
+    b'a', 0, b'/', 0, b'b', 0, b'.', 0, b'o', 0, b'g', 0, b'g', 0,
+    // Media, 7 members
+    0x07,
+    0x07, 0x00, 0x00, 0x00,
+    b'a', 0, b'/', 0, b'b', 0, b'.', 0, b'o', 0, b'g', 0, b'g', 0,
+    0x05, 0x00, 0x00, 0x00,
+    b'b', 0, b'.', 0, b'o', 0, b'g', 0, b'g', 0,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x2A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x01,
+    0x01, 0x00, 0x00, 0x00
+];
+
+#[rustfmt::skip]
+pub const NULL_MEMBERS_CATALOG: &[u8] = &[
+    // TableCatalog, 1 member (no PatchPacks)
+    0x01,
+    // Table: 2 entries
+    0x02, 0x00, 0x00, 0x00,
+    // key "A.zip" (UTF-8)
+    0xFA, 0xFF, 0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, b'A', b'.', b'z', b'i', b'p',
+    // TableBundle, 8 members: null Name, null Includes
+    0x08,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    // key "B.zip" (UTF-8)
+    0xFA, 0xFF, 0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, b'B', b'.', b'z', b'i', b'p',
+    // TableBundle, 2 members: Name, Size
+    0x02,
+    0xFA, 0xFF, 0xFF, 0xFF, 0x05, 0x00, 0x00, 0x00, b'B', b'.', b'z', b'i', b'p',
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+];
+
+#[rustfmt::skip]
+pub const NULL_MEMBERS_MEDIA: &[u8] = &[
+    // MediaCatalog, 1 member
+    0x01,
+    // Table: 1 entry
+    0x01, 0x00, 0x00, 0x00,
+    // key "a" (UTF-16)
+    0x01, 0x00, 0x00, 0x00, b'a', 0,
+    // Media, 2 members: Path, null FileName
+    0x02,
+    0x01, 0x00, 0x00, 0x00, b'a', 0,
+    0xFF, 0xFF, 0xFF, 0xFF
+];
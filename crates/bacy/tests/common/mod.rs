@@ -0,0 +1,46 @@
+//! Helpers shared by the integration tests. Each test crate only uses some of
+//! them.
+#![allow(dead_code)]
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, io, process};
+
+pub mod catalogs;
+
+/// A fresh directory under the system temp dir, removed again on drop, so
+/// also when the test panics.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let id = NEXT.fetch_add(1, Ordering::Relaxed);
+            let path = env::temp_dir().join(format!("bacy-{name}-{}-{id}", process::id()));
+            match fs::create_dir(&path) {
+                Ok(()) => return Self(path),
+                // Left over from an earlier run with the same process id.
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => panic!("cannot create {}: {e}", path.display())
+            }
+        }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Path { &self.0 }
+}
+
+impl AsRef<Path> for TempDir {
+    #[inline]
+    fn as_ref(&self) -> &Path { &self.0 }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
@@ -0,0 +1,43 @@
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bacy::error::HashError;
+use bacy::hash::crc;
+use common::TempDir;
+
+mod common;
+
+#[tokio::test]
+async fn matches_sync_semantics() {
+    let dir = TempDir::new("crc-async");
+    let path = dir.join("data");
+    let data: Vec<u8> = (0..50_000u32).map(|i| (i % 253) as u8).collect();
+    fs::write(&path, &data).unwrap();
+
+    let expected = crc::compute_streaming(&path, 0x2000, None).unwrap();
+    assert_eq!(crc::compute(&path).await.unwrap(), expected);
+    assert_eq!(
+        crc::compute_streaming_async(&path, 4096, Some(b"tail")).await.unwrap(),
+        crc::compute_streaming(&path, 4096, Some(b"tail")).unwrap()
+    );
+    assert_eq!(crc::compute_reader(data.as_slice(), 4096, None, |_| {}).await.unwrap(), expected);
+    assert!(crc::compare_async(&path, expected).await.is_ok());
+    assert!(matches!(crc::compare_async(&path, !expected).await, Err(HashError::Mismatch { .. })));
+
+    let mut reports = Vec::new();
+    crc::compute_with_progress(&path, 4096, None, None, |p| reports.push(p)).await.unwrap();
+    assert_eq!(reports.last().map(|p| (p.read, p.total)), Some((50_000, Some(50_000))));
+    assert!(reports.windows(2).all(|w| w[0].read < w[1].read));
+
+    let cancel = AtomicBool::new(false);
+    let result = crc::compute_with_progress(&path, 4096, None, Some(&cancel), |p| {
+        if p.read >= 8192 {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    })
+    .await;
+    assert!(matches!(result, Err(HashError::Cancelled)));
+
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(crc::compute(&path).await, Err(HashError::InvalidPath)));
+}
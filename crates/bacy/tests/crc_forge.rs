@@ -0,0 +1,199 @@
+use std::fs;
+
+use bacy::error::{CrcSpecError, HashError};
+use bacy::hash::crc_spec::CrcSpec;
+use bacy::utils::crc_manipulator::*;
+use common::TempDir;
+
+mod common;
+
+#[test]
+fn forges_batches_in_order() {
+    let dir = TempDir::new("forge-batch");
+    let write = |name: &str, data: &[u8]| {
+        let path = dir.join(name);
+        fs::write(&path, data).unwrap();
+        path
+    };
+
+    let original = write("original", b"original contents");
+    let jobs = [
+        ForgeJob::matching(write("modded", b"modded contents"), &original),
+        ForgeJob::crc(write("crc", b"other"), 0xDEADBEEF),
+        ForgeJob::crc(write("same", b"same"), crc32fast::hash(b"same")),
+        ForgeJob::matching(write("orphan", b"orphan"), dir.join("missing")),
+        ForgeJob::crc(dir.join("absent"), 0)
+    ];
+
+    let reports = forge_batch_with_threads(&jobs, 3);
+    let statuses: Vec<_> = reports.iter().map(|r| r.result.as_ref().ok().copied()).collect();
+    assert_eq!(statuses, [
+        Some(ForgeStatus::Forged),
+        Some(ForgeStatus::Forged),
+        Some(ForgeStatus::AlreadyMatching),
+        None,
+        None
+    ]);
+    assert!(reports.iter().zip(&jobs).all(|(report, job)| report.path == job.path));
+
+    assert_eq!(
+        crc32fast::hash(&fs::read(&jobs[0].path).unwrap()),
+        crc32fast::hash(b"original contents")
+    );
+    assert_eq!(crc32fast::hash(&fs::read(&jobs[1].path).unwrap()), 0xDEADBEEF);
+    assert_eq!(fs::read(&jobs[2].path).unwrap(), b"same");
+    assert_eq!(fs::read(&jobs[3].path).unwrap(), b"orphan");
+    assert!(matches!(reports[3].result, Err(HashError::InvalidPath)));
+}
+
+#[test]
+fn rejects_duplicate_batch_paths() {
+    let dir = TempDir::new("forge-duplicates");
+    fs::write(dir.join("shared"), b"shared").unwrap();
+    fs::write(dir.join("single"), b"single").unwrap();
+
+    let jobs = [
+        ForgeJob::crc(dir.join("shared"), 0x11111111),
+        ForgeJob::crc(dir.join("single"), 0x22222222),
+        ForgeJob::crc(dir.join(".").join("shared"), 0x33333333)
+    ];
+
+    let reports = forge_batch_with_threads(&jobs, 2);
+    assert!(
+        matches!(&reports[0].result, Err(HashError::DuplicatePath(path)) if *path == jobs[0].path)
+    );
+    assert!(matches!(reports[1].result, Ok(ForgeStatus::Forged)));
+    assert!(
+        matches!(&reports[2].result, Err(HashError::DuplicatePath(path)) if *path == jobs[2].path)
+    );
+    assert_eq!(fs::read(dir.join("shared")).unwrap(), b"shared");
+
+    // Matching a file that is forged in the same batch would race with it.
+    let jobs = [
+        ForgeJob::matching(dir.join("single"), dir.join("shared")),
+        ForgeJob::crc(dir.join("shared"), 0x44444444),
+        ForgeJob::matching(dir.join("shared"), dir.join("shared"))
+    ];
+    let reports = forge_batch_with_threads(&jobs[..2], 2);
+    assert!(matches!(
+        &reports[0].result,
+        Err(HashError::SourceInBatch(path)) if *path == dir.join("shared")
+    ));
+    assert!(matches!(reports[1].result, Ok(ForgeStatus::Forged)));
+    assert_eq!(crc32fast::hash(&fs::read(dir.join("single")).unwrap()), 0x22222222);
+
+    let reports = forge_batch(&jobs[2..]);
+    assert!(matches!(reports[0].result, Ok(ForgeStatus::AlreadyMatching)));
+}
+
+#[test]
+fn forges_at_offsets() {
+    let dir = TempDir::new("forge-at");
+    let data: Vec<u8> = (0..=255).cycle().take(5000).collect();
+
+    for (offset, mode) in [
+        (0, PatchMode::Overwrite),
+        (1234, PatchMode::Overwrite),
+        (4996, PatchMode::Overwrite),
+        (0, PatchMode::Insert),
+        (2500, PatchMode::Insert),
+        (5000, PatchMode::Insert)
+    ] {
+        let path = dir.join("file");
+        fs::write(&path, &data).unwrap();
+        CrcManipulator::new(&path).forge_crc_at(0x12345678, offset, mode).unwrap();
+
+        let forged = fs::read(&path).unwrap();
+        let offset = offset as usize;
+        assert_eq!(crc32fast::hash(&forged), 0x12345678);
+        assert_eq!(forged[..offset], data[..offset]);
+        match mode {
+            PatchMode::Overwrite => assert_eq!(forged[offset + 4..], data[offset + 4..]),
+            PatchMode::Insert => assert_eq!(forged[offset + 4..], data[offset..])
+        }
+    }
+
+    let manipulator = CrcManipulator::new(dir.join("file"));
+    assert!(matches!(
+        manipulator.forge_crc_at(0, 5001, PatchMode::Overwrite),
+        Err(HashError::Spec(CrcSpecError::OutOfRange { offset: 5001, len: 5004 }))
+    ));
+    assert!(matches!(
+        manipulator.forge_crc_at(0, 5005, PatchMode::Insert),
+        Err(HashError::Spec(CrcSpecError::OutOfRange { .. }))
+    ));
+}
+
+#[test]
+fn forges_other_specs() {
+    let dir = TempDir::new("forge-spec");
+    let path = dir.join("file");
+    let data: Vec<u8> = (0..=255).cycle().take(3000).collect();
+
+    for spec in [CrcSpec::CRC32C, CrcSpec::CRC64_XZ, CrcSpec::CRC64_ECMA_182] {
+        let target = 0x0123456789ABCDEF & spec.mask();
+        let manipulator = CrcManipulator::new(&path).with_spec(spec);
+        let size = spec.width() as usize / 8;
+
+        fs::write(&path, &data).unwrap();
+        manipulator.forge_checksum(target).unwrap();
+        assert_eq!(spec.checksum(&fs::read(&path).unwrap()), target);
+        assert_eq!(manipulator.plan(target).unwrap(), None);
+
+        for (offset, mode) in [(1000, PatchMode::Overwrite), (1000, PatchMode::Insert)] {
+            fs::write(&path, &data).unwrap();
+            manipulator.forge_checksum_at(target ^ 1, offset, mode).unwrap();
+
+            let forged = fs::read(&path).unwrap();
+            assert_eq!(spec.checksum(&forged), target ^ 1, "{spec:?} {mode:?}");
+            assert_eq!(forged.len(), data.len() + size * (mode == PatchMode::Insert) as usize);
+        }
+
+        fs::write(&path, &data).unwrap();
+        let reports = forge_batch(&[ForgeJob::checksum(&path, spec, target)]);
+        assert_eq!(reports[0].target_checksum, Some(target));
+        assert_eq!(reports[0].target_crc, None);
+        assert!(matches!(reports[0].result, Ok(ForgeStatus::Forged)));
+        assert!(spec.compare(&path, target).is_ok());
+    }
+
+    let crc5 = CrcSpec::new(5, 0x05, 0x1F, true, true, 0x1F).unwrap();
+    assert!(matches!(
+        CrcManipulator::new(&path).with_spec(crc5).forge_checksum(0),
+        Err(HashError::Spec(CrcSpecError::UnsupportedWidth(5)))
+    ));
+    assert!(matches!(
+        CrcSpec::CRC64_XZ.compare(&path, 0),
+        Err(HashError::ChecksumMismatch { expected: 0, .. })
+    ));
+}
+
+#[test]
+fn forges_in_memory() {
+    for data in [&b""[..], b"a", b"hello world", &[0xFF; 1000]] {
+        let patch = forge_bytes_for(data, 0xCAFEBABE);
+        let mut forged = data.to_vec();
+        forged.extend_from_slice(&patch);
+        assert_eq!(crc32fast::hash(&forged), 0xCAFEBABE);
+
+        let mut extended = data.to_vec();
+        extend_with_forge(&mut extended, 0xCAFEBABE);
+        assert_eq!(extended, forged);
+    }
+}
+
+#[test]
+fn plans_without_writing() {
+    let dir = TempDir::new("forge-plan");
+    let path = dir.join("file");
+    fs::write(&path, b"contents").unwrap();
+
+    let patch = plan_forge(&path, 0x0BADF00D).unwrap().unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"contents");
+    assert_eq!(patch, forge_bytes_for(b"contents", 0x0BADF00D));
+    assert_eq!(plan_forge(&path, crc32fast::hash(b"contents")).unwrap(), None);
+    assert_eq!(CrcManipulator::new(&path).plan(0x0BADF00D).unwrap().as_deref(), Some(&patch[..]));
+
+    CrcManipulator::new(&path).forge_crc(0x0BADF00D).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), [&b"contents"[..], &patch].concat());
+}
@@ -0,0 +1,42 @@
+use std::fs;
+
+use bacy::error::HashError;
+use bacy::hash::crc;
+use common::TempDir;
+
+mod common;
+
+#[test]
+fn combine_matches_concatenation() {
+    let (a, b) = (b"hello ".as_slice(), b"world".as_slice());
+    let combined = crc::combine(crc32fast::hash(a), crc32fast::hash(b), b.len() as u64);
+    assert_eq!(combined, crc32fast::hash(b"hello world"));
+    assert_eq!(crc::combine(crc32fast::hash(a), 0, 0), crc32fast::hash(a));
+}
+
+#[test]
+fn parallel_matches_streaming() {
+    let dir = TempDir::new("crc-parallel");
+    let path = dir.join("data");
+
+    for len in [0, 1, 4095, 4096, 4097, 100_000, 1 << 20] {
+        let data: Vec<u8> = (0..len).map(|i| (i * 31 % 251) as u8).collect();
+        fs::write(&path, &data).unwrap();
+
+        let expected = crc::compute_streaming(&path, 0x2000, None).unwrap();
+        assert_eq!(crc::compute_bytes_parallel(&data, 4096), expected);
+        assert_eq!(crc::compute_parallel(&path, 4096).unwrap(), expected);
+        for threads in [1, 3, 8] {
+            assert_eq!(
+                crc::compute_parallel_with_threads(&path, 10_000, threads).unwrap(),
+                expected
+            );
+        }
+
+        #[cfg(feature = "mmap")]
+        assert_eq!(crc::compute_mmap(&path, 4096).unwrap(), expected);
+    }
+
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(crc::compute_parallel(&path, 4096), Err(HashError::InvalidPath)));
+}
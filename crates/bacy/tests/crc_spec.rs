@@ -0,0 +1,84 @@
+use bacy::error::CrcSpecError;
+use bacy::hash::crc;
+use bacy::hash::crc_spec::CrcSpec;
+use bacy::utils::crc_manipulator::forge_bytes_for;
+use proptest::prelude::*;
+
+const CHECK: &[u8] = b"123456789";
+
+fn specs() -> [CrcSpec; 7] {
+    [
+        CrcSpec::CRC32,
+        CrcSpec::CRC32C,
+        CrcSpec::CRC32_BZIP2,
+        CrcSpec::CRC64_XZ,
+        CrcSpec::CRC64_ECMA_182,
+        // CRC-16/ARC
+        CrcSpec::new(16, 0x8005, 0, true, true, 0).unwrap(),
+        // CRC-16/IBM-3740
+        CrcSpec::new(16, 0x1021, 0xFFFF, false, false, 0).unwrap()
+    ]
+}
+
+#[test]
+fn matches_catalogue_check_values() {
+    let expected = [
+        0xCBF43926,
+        0xE3069283,
+        0xFC891918,
+        0x995DC9BBDF1939FA,
+        0x6C40DF5F0B497347,
+        0xBB3D,
+        0x29B1
+    ];
+    for (spec, expected) in specs().iter().zip(expected) {
+        assert_eq!(spec.checksum(CHECK), expected, "{spec:?}");
+    }
+
+    // CRC-5/USB and CRC-12/UMTS exercise sub-byte widths and mixed reflection.
+    assert_eq!(CrcSpec::new(5, 0x05, 0x1F, true, true, 0x1F).unwrap().checksum(CHECK), 0x19);
+    assert_eq!(CrcSpec::new(12, 0x80F, 0, false, true, 0).unwrap().checksum(CHECK), 0xDAF);
+}
+
+#[test]
+fn rejects_invalid_specs() {
+    assert!(matches!(CrcSpec::new(0, 1, 0, false, false, 0), Err(CrcSpecError::InvalidWidth(0))));
+    assert!(matches!(CrcSpec::new(65, 1, 0, false, false, 0), Err(CrcSpecError::InvalidWidth(65))));
+    assert!(matches!(
+        CrcSpec::new(8, 0x107, 0, false, false, 0),
+        Err(CrcSpecError::ParameterTooWide(8))
+    ));
+    assert!(matches!(
+        CrcSpec::new(8, 0x06, 0, false, false, 0),
+        Err(CrcSpecError::EvenPolynomial(0x06))
+    ));
+
+    let crc5 = CrcSpec::new(5, 0x05, 0x1F, true, true, 0x1F).unwrap();
+    assert!(matches!(crc5.forge_bytes_for(CHECK, 0), Err(CrcSpecError::UnsupportedWidth(5))));
+}
+
+proptest! {
+    #[test]
+    fn crc32_agrees_with_existing_code(data: Vec<u8>, target: u32) {
+        prop_assert_eq!(CrcSpec::CRC32.checksum(&data), crc::compute_bytes(&data, None) as u64);
+
+        let patch = CrcSpec::CRC32.forge_bytes_for(&data, target as u64).unwrap();
+        prop_assert_eq!(&patch[..], &forge_bytes_for(&data, target)[..]);
+    }
+
+    #[test]
+    fn forges_every_spec(data: Vec<u8>, target: u64, offset: prop::sample::Index) {
+        for spec in specs() {
+            let target = target & spec.mask();
+
+            let mut appended = data.clone();
+            appended.extend_from_slice(&spec.forge_bytes_for(&data, target).unwrap());
+            prop_assert_eq!(spec.checksum(&appended), target);
+
+            let mut patched = appended.clone();
+            let offset = offset.index(patched.len() - spec.width() as usize / 8 + 1);
+            spec.forge_in_place(&mut patched, offset, target ^ 1).unwrap();
+            prop_assert_eq!(spec.checksum(&patched), target ^ 1);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::io::Cursor;
+
+use bacy::utils::dictionary::NameDictionary;
+use bacy::utils::strategy::get_file_path;
+
+fn hashed(name: &str, crc: Option<i64>, to_lower: bool) -> String {
+    get_file_path(name, crc, false, to_lower).to_str().unwrap().to_string()
+}
+
+#[test]
+fn resolves_raw_and_lowercase_hashes() {
+    let dictionary: NameDictionary =
+        ["Audio\\VOC_JP\\Hello.ogg", "Excel.zip"].into_iter().collect();
+
+    assert_eq!(dictionary.lookup(&hashed("Hello.ogg", None, false)), Some(("Hello.ogg", None)));
+    assert_eq!(
+        dictionary.lookup(&hashed("Hello.ogg", Some(42), true)),
+        Some(("Hello.ogg", Some(42)))
+    );
+    assert_eq!(
+        dictionary.lookup(&format!("dir/{}", hashed("Excel.zip", Some(7), true))),
+        Some(("Excel.zip", Some(7)))
+    );
+}
+
+#[test]
+fn rejects_unknown_or_malformed_names() {
+    let dictionary: NameDictionary = ["Excel.zip"].into_iter().collect();
+
+    assert_eq!(dictionary.lookup(&hashed("Other.zip", None, true)), None);
+    assert_eq!(dictionary.lookup("Excel.zip"), None);
+    assert_eq!(dictionary.lookup(&format!("{}_x", hashed("Excel.zip", None, true))), None);
+}
+
+#[test]
+fn reads_wordlists() {
+    let mut dictionary = NameDictionary::new();
+    let count = dictionary.insert_wordlist(Cursor::new("A.bytes\n\n  B.bytes  \n")).unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(dictionary.lookup(&hashed("b.bytes", None, false)), Some(("B.bytes", None)));
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "sqlite")]
+
+use bacy::crypto::table;
+use bacy::error::ExcelDbError;
+use bacy::table::exceldb::{ExcelDb, record_key_name};
+use rusqlite::Connection;
+
+fn database() -> ExcelDb {
+    let connection = Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(
+            r#"
+            CREATE TABLE "CharacterDBSchema" ("Key" INTEGER, "Bytes" BLOB);
+            CREATE TABLE "Odd""NameDBSchema" ("Bytes" BLOB);
+            CREATE TABLE "NoBytesDBSchema" ("Key" INTEGER);
+            CREATE TABLE "Other" ("Bytes" BLOB);
+            "#
+        )
+        .unwrap();
+
+    for (table_name, key_name, rows) in [
+        ("CharacterDBSchema", "character", &[&b"first row"[..], b"second"][..]),
+        ("Odd\"NameDBSchema", "odd\"name", &[&b"quoted"[..]][..])
+    ] {
+        let query =
+            format!("INSERT INTO \"{}\" (Bytes) VALUES (?1)", table_name.replace('"', "\"\""));
+        for row in rows {
+            let mut bytes = row.to_vec();
+            table::xor(key_name, &mut bytes);
+            connection.execute(&query, [bytes]).unwrap();
+        }
+    }
+
+    ExcelDb::from_connection(connection)
+}
+
+#[test]
+fn lists_schema_tables() {
+    let db = database();
+    assert_eq!(db.tables().unwrap(), ["CharacterDBSchema", "NoBytesDBSchema", "Odd\"NameDBSchema"]);
+    assert_eq!(db.key("CharacterDBSchema").unwrap(), table::create_key("CharacterExcel"));
+    assert_eq!(record_key_name("CharacterDBSchema").as_deref(), Some("CharacterExcel"));
+    assert_eq!(record_key_name("DBSchema"), None);
+    assert!(matches!(db.key("Other"), Err(ExcelDbError::TableNotFound(_))));
+}
+
+#[test]
+fn decrypts_rows() {
+    let db = database();
+    let rows = db.rows("CharacterDBSchema").unwrap();
+    assert_eq!(rows.iter().map(|row| row.row_id).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(rows[0].bytes, b"first row");
+    assert_eq!(rows[1].bytes, b"second");
+
+    let rows = db.rows("Odd\"NameDBSchema").unwrap();
+    assert_eq!(rows[0].bytes, b"quoted");
+}
+
+#[test]
+fn rejects_unknown_tables() {
+    let db = database();
+    assert!(matches!(db.rows("MissingDBSchema"), Err(ExcelDbError::TableNotFound(_))));
+    assert!(matches!(db.rows("Other"), Err(ExcelDbError::TableNotFound(_))));
+    assert!(matches!(db.rows("NoBytesDBSchema"), Err(ExcelDbError::MissingBytesColumn(_))));
+    assert!(matches!(
+        db.rows("x') UNION SELECT 1 --DBSchema"),
+        Err(ExcelDbError::TableNotFound(_))
+    ));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn decrypts_row_fields() {
+    use bacy::crypto::table::{TableDecrypt, TableEncrypt, TableRecord};
+
+    #[derive(Debug, PartialEq, TableDecrypt, TableEncrypt)]
+    struct ItemExcelT {
+        id: i64,
+        name: String
+    }
+
+    /// A stand-in for the FlatBuffer payload: the id followed by the name.
+    fn parse(bytes: &[u8]) -> ItemExcelT {
+        let (id, name) = bytes.split_at(8);
+        ItemExcelT {
+            id: i64::from_le_bytes(id.try_into().unwrap()),
+            name: String::from_utf8(name.to_vec()).unwrap()
+        }
+    }
+
+    let key = table::create_key(ItemExcelT::KEY_NAME);
+    let mut bytes = table::encrypt_i64(42, &key).to_le_bytes().to_vec();
+    bytes.extend_from_slice(table::encrypt_string("Eleph", &key).as_bytes());
+    table::xor("item", &mut bytes);
+
+    let connection = Connection::open_in_memory().unwrap();
+    connection.execute_batch(r#"CREATE TABLE "ItemDBSchema" ("Bytes" BLOB);"#).unwrap();
+    connection.execute(r#"INSERT INTO "ItemDBSchema" (Bytes) VALUES (?1)"#, [bytes]).unwrap();
+    let db = ExcelDb::from_connection(connection);
+    assert_eq!(db.key("ItemDBSchema").unwrap(), key);
+
+    let mut record = parse(&db.rows("ItemDBSchema").unwrap()[0].bytes);
+    record.decrypt_record().unwrap();
+    assert_eq!(record, ItemExcelT {
+        id: 42,
+        name: "Eleph".to_string()
+    });
+}
@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use bacy::catalog::media::{MediaCatalog, MediaType};
+use bacy::catalog::table::TableCatalog;
+use bacy::error::MemoryPackError;
+use bacy::format::memorypack::*;
+use bacy::hash::xxhash::calculate_hash64_str;
+use common::catalogs::*;
+
+mod common;
+
+#[rustfmt::skip]
+const UTF16_STRING: &[u8] = &[
+    0x03, 0x00, 0x00, 0x00,
+    b'a', 0x00, 0xE9, 0x00, b'!', 0x00
+];
+
+#[rustfmt::skip]
+const UTF8_STRING: &[u8] = &[
+    0xFC, 0xFF, 0xFF, 0xFF,
+    0x02, 0x00, 0x00, 0x00,
+    b'a', 0xC3, 0xA9
+];
+
+#[test]
+fn reads_utf16_string() {
+    let mut reader = MemoryPackReader::new(UTF16_STRING);
+    assert_eq!(reader.read_string().unwrap().as_deref(), Some("aé!"));
+    assert_eq!(reader.string_encoding(), Some(StringEncoding::Utf16));
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn reads_utf8_string() {
+    let mut reader = MemoryPackReader::new(UTF8_STRING);
+    assert_eq!(reader.read_string().unwrap().as_deref(), Some("aé"));
+    assert_eq!(reader.string_encoding(), Some(StringEncoding::Utf8));
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn reads_null_values() {
+    let null = (-1i32).to_le_bytes();
+    assert_eq!(from_bytes::<Option<String>>(&null).unwrap(), None);
+    assert_eq!(from_bytes::<String>(&null).unwrap(), "");
+    assert!(from_bytes::<Vec<i32>>(&null).unwrap().is_empty());
+
+    let mut reader = MemoryPackReader::new(&[0xFF]);
+    assert!(matches!(reader.read_members("Object", 1), Err(MemoryPackError::NullObject("Object"))));
+}
+
+#[test]
+fn rejects_malformed_input() {
+    assert!(matches!(from_bytes::<i64>(&[0; 7]), Err(MemoryPackError::UnexpectedEof)));
+    assert!(matches!(
+        from_bytes::<Vec<u8>>(&(-2i32).to_le_bytes()),
+        Err(MemoryPackError::InvalidLength(-2))
+    ));
+    assert!(matches!(
+        from_bytes::<Vec<u8>>(&[0xFF, 0xFF, 0xFF, 0x7F]),
+        Err(MemoryPackError::UnexpectedEof)
+    ));
+
+    let unpaired = [0x01, 0x00, 0x00, 0x00, 0x3D, 0xD8];
+    assert!(matches!(from_bytes::<String>(&unpaired), Err(MemoryPackError::FromUtf16Error(_))));
+
+    let mut reader = MemoryPackReader::new(&[0x03]);
+    assert!(matches!(
+        reader.read_members("Object", 2),
+        Err(MemoryPackError::UnsupportedMemberCount { count: 3, expected: 2, .. })
+    ));
+}
+
+#[test]
+fn writes_fixtures() {
+    let mut writer = MemoryPackWriter::with_encoding(StringEncoding::Utf8);
+    writer.write_string("aé").unwrap();
+    assert_eq!(writer.as_bytes(), UTF8_STRING);
+
+    let mut writer = MemoryPackWriter::new();
+    writer.write_string("aé!").unwrap();
+    assert_eq!(writer.as_bytes(), UTF16_STRING);
+    let mut writer = MemoryPackWriter::new();
+    writer.write_object_header(0).unwrap();
+    writer.write_object_header(249).unwrap();
+    assert_eq!(writer.as_bytes(), [0, 249]);
+    for count in 250..=255 {
+        assert!(matches!(
+            writer.write_object_header(count),
+            Err(MemoryPackError::InvalidMemberCount(c)) if c == count
+        ));
+    }
+    assert_eq!(writer.as_bytes().len(), 2);
+}
+
+#[test]
+fn round_trips_collections() {
+    let entries = vec![("one".to_string(), vec![1i64, 2]), ("two".to_string(), Vec::new())];
+
+    let mut writer = MemoryPackWriter::new();
+    writer.write_map(&entries).unwrap();
+    writer.write_null_object();
+    writer.write(&Some("x".to_string())).unwrap();
+    writer.write(&None::<String>).unwrap();
+
+    let bytes = writer.into_inner();
+    let mut reader = MemoryPackReader::new(&bytes);
+    assert_eq!(reader.read_map::<String, Vec<i64>>().unwrap(), entries);
+    assert_eq!(reader.read_object_header().unwrap(), None);
+    assert_eq!(reader.read::<Option<String>>().unwrap().as_deref(), Some("x"));
+    assert_eq!(reader.read::<Option<String>>().unwrap(), None);
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn parses_table_catalog() {
+    let catalog = TableCatalog::from_bytes(TABLE_CATALOG).unwrap();
+    assert_eq!(catalog.len(), 1);
+    assert_eq!(catalog.packs().count(), 0);
+
+    let bundle = catalog.bundle("A.zip").unwrap();
+    assert_eq!((bundle.size, bundle.crc), (0x10, 0x12345678));
+    assert!(bundle.is_in_build && !bundle.is_changed && bundle.is_prologue);
+    assert_eq!(bundle.includes(), ["X.bytes"]);
+    assert_eq!(catalog.bundle_for_table("x.BYTES"), Some(bundle));
+
+    assert!(TableCatalog::from_bytes(&TABLE_CATALOG[..TABLE_CATALOG.len() - 1]).is_err());
+}
+
+#[test]
+fn parses_media_catalog() {
+    let catalog = MediaCatalog::from_bytes(MEDIA_CATALOG).unwrap();
+    let media = catalog.get("A/B.ogg").unwrap();
+
+    assert_eq!(media.file_name(), "b.ogg");
+    assert_eq!((media.size, media.crc), (0x20, 0x2A));
+    assert!(!media.is_prologue && media.is_split_download);
+    assert_eq!(media.media_type, MediaType::Audio);
+
+    let hash = calculate_hash64_str("b.ogg");
+    assert_eq!(media.resolve("root"), Path::new("root/a").join(format!("{hash}_42")));
+}
+
+#[test]
+fn writes_catalogs_back() {
+    let mut tables = TableCatalog::from_bytes(TABLE_CATALOG).unwrap();
+    assert_eq!(tables.to_bytes().unwrap(), TABLE_CATALOG);
+
+    assert!(tables.update_bytes("A.zip", b"modded"));
+    assert!(!tables.update("B.zip", 0, 0));
+
+    let reparsed = TableCatalog::from_bytes(&tables.to_bytes().unwrap()).unwrap();
+    let bundle = reparsed.bundle("A.zip").unwrap();
+    assert_eq!(bundle.size, 6);
+    assert_eq!(bundle.crc, crc32fast::hash(b"modded") as i64);
+    assert_eq!(bundle.includes(), ["X.bytes"]);
+
+    let mut media = MediaCatalog::from_bytes(MEDIA_CATALOG).unwrap();
+    assert_eq!(media.to_bytes().unwrap(), MEDIA_CATALOG);
+
+    assert!(media.update("a/b.ogg", 7, 99));
+    let reparsed = MediaCatalog::from_bytes(&media.to_bytes().unwrap()).unwrap();
+    assert_eq!(reparsed.get("a/b.ogg").map(|m| (m.size, m.crc)), Some((7, 99)));
+
+    let mut tables = TableCatalog::from_bytes(NULL_MEMBERS_CATALOG).unwrap();
+    let bundle = tables.bundle("A.zip").unwrap();
+    assert_eq!((bundle.name.as_deref(), bundle.includes.as_deref()), (None, None));
+    assert_eq!((bundle.name(), bundle.includes()), ("", &[][..]));
+    assert_eq!(tables.bundle("B.zip").map(|b| (b.size, b.crc)), Some((0x20, 0)));
+    assert_eq!(tables.to_bytes().unwrap(), NULL_MEMBERS_CATALOG);
+
+    assert!(tables.update("B.zip", 0x30, 0x99));
+    let bytes = tables.to_bytes().unwrap();
+    // Everything before B.zip's object is untouched; the object grows to 3 members
+    // for the CRC.
+    let unchanged = NULL_MEMBERS_CATALOG.len() - 22;
+    assert_eq!(bytes[..unchanged], NULL_MEMBERS_CATALOG[..unchanged]);
+    assert_eq!(bytes[unchanged], 0x03);
+    let reparsed = TableCatalog::from_bytes(&bytes).unwrap();
+    assert_eq!(reparsed.bundle("B.zip").map(|b| (b.size, b.crc)), Some((0x30, 0x99)));
+    assert_eq!(reparsed.bundle("A.zip"), tables.bundle("A.zip"));
+
+    let media = MediaCatalog::from_bytes(NULL_MEMBERS_MEDIA).unwrap();
+    let entry = media.get("a").unwrap();
+    assert_eq!((entry.path(), entry.file_name.as_deref()), ("a", None));
+    assert_eq!(media.to_bytes().unwrap(), NULL_MEMBERS_MEDIA);
+
+    let null = [0xFF];
+    assert_eq!(TableCatalog::from_bytes(&null).unwrap().to_bytes().unwrap(), null);
+    assert_eq!(MediaCatalog::from_bytes(&null).unwrap().to_bytes().unwrap(), null);
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::io::Cursor;
+
+use bacy::crypto::md5;
+use bacy::error::HashError;
+use bacy::hash::multi::{Digests, MultiHasher};
+use bacy::hash::{crc, sha, xxhash};
+use common::TempDir;
+
+mod common;
+
+fn all() -> MultiHasher {
+    MultiHasher::new()
+        .with_crc32()
+        .with_md5()
+        .with_hmac_md5(b"key")
+        .with_sha256()
+        .with_xxhash32()
+        .with_xxhash64()
+}
+
+#[test]
+fn matches_individual_hashes() {
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 241) as u8).collect();
+
+    let expected = Digests {
+        crc32: Some(crc::compute_bytes(&data, None)),
+        md5: Some(md5::compute_hash(&data)),
+        hmac_md5: Some(md5::compute_hash_hmac(&data, b"key")),
+        sha256: Some(sha::compute(&data)),
+        xxhash32: Some(xxhash::calculate_hash(&data)),
+        xxhash64: Some(xxhash::calculate_hash64(&data))
+    };
+
+    assert_eq!(all().hash_bytes(&data), expected);
+    assert_eq!(all().hash_reader(Cursor::new(&data)).unwrap(), expected);
+
+    let dir = TempDir::new("multi-hasher");
+    let path = dir.join("data");
+    fs::write(&path, &data).unwrap();
+    assert_eq!(all().hash_file(&path).unwrap(), expected);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(all().hash_file(&path), Err(HashError::InvalidPath)));
+}
+
+#[test]
+fn only_computes_selected_digests() {
+    let digests = MultiHasher::new().with_crc32().with_sha256().hash_bytes(b"abc");
+
+    assert_eq!(digests.crc32, Some(crc::compute_bytes(b"abc", None)));
+    assert_eq!(digests.sha256, Some(sha::compute(b"abc")));
+    assert_eq!(
+        (digests.md5, digests.hmac_md5, digests.xxhash32, digests.xxhash64),
+        (None, None, None, None)
+    );
+    assert_eq!(MultiHasher::new().hash_bytes(b"abc"), Digests::default());
+}
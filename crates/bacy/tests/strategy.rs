@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use bacy::error::StrategyError;
+use bacy::utils::strategy::{FileNaming, HashedFileName, get_file_path};
+
+#[test]
+fn matches_get_file_path() {
+    for (crc, no_hash) in [(None, false), (None, true), (Some(1234), false)] {
+        let expected = get_file_path("dir/Foo.bytes", crc, no_hash, true);
+        let name = HashedFileName::new("dir/Foo.bytes", crc, no_hash, true).unwrap();
+        assert_eq!(expected.file_name().unwrap().to_str(), Some(name.to_string().as_str()));
+    }
+}
+
+#[test]
+fn parses_and_round_trips() {
+    for name in ["123", "123_456", "Excel.zip", "12_ab", "_12"] {
+        assert_eq!(name.parse::<HashedFileName>().unwrap().to_string(), name);
+    }
+
+    assert_eq!(HashedFileName::parse("123_456").unwrap(), HashedFileName::HashWithCrc {
+        hash: 123,
+        crc: 456
+    });
+    assert!(!HashedFileName::parse("12_ab").unwrap().is_hashed());
+}
+
+#[test]
+fn rejects_invalid_names() {
+    assert!(matches!(HashedFileName::parse(""), Err(StrategyError::EmptyName)));
+    assert!(matches!(HashedFileName::parse("a/b"), Err(StrategyError::InvalidName(_))));
+    assert!(matches!(
+        HashedFileName::parse("99999999999999999999"),
+        Err(StrategyError::InvalidHash(_))
+    ));
+    assert!(matches!(HashedFileName::parse("1_4294967296"), Err(StrategyError::InvalidCrc(_))));
+    assert!(matches!(
+        HashedFileName::new("dir/..", None, false, true),
+        Err(StrategyError::EmptyName)
+    ));
+    assert!(matches!(
+        HashedFileName::new("a.bytes", Some(-1), false, true),
+        Err(StrategyError::InvalidCrc(_))
+    ));
+}
+
+#[test]
+fn naming_matches_strategy() {
+    let naming = FileNaming::default();
+    let path = naming.path_for("Audio\\A.ogg", 7);
+    let hash = bacy::hash::xxhash::calculate_hash64_str("a.ogg");
+    assert_eq!(path, Path::new("Audio").join(format!("{hash}_7")));
+
+    assert_eq!(FileNaming::PLAIN.path_for("Excel.zip", 7), Path::new("Excel.zip"));
+}
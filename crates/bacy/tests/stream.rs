@@ -0,0 +1,159 @@
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+use bacy::crypto::stream::*;
+use bacy::crypto::table;
+
+const NAME: &str = "CharacterExcelTable";
+
+// Unaligned positions on both sides of a 624-word block and of the 128-block
+// checkpoint interval.
+const OFFSETS: [u64; 11] = [0, 1, 2, 3, 5, 2495, 2497, 4095, 319_487, 319_489, 958_466];
+
+fn plaintext(length: usize) -> Vec<u8> { (0..length).map(|i| (i % 251) as u8).collect() }
+
+fn encrypted(length: usize) -> Vec<u8> {
+    let mut data = plaintext(length);
+    table::xor(NAME, &mut data);
+    data
+}
+
+#[test]
+fn seek_matches_table_xor() {
+    let length = OFFSETS[OFFSETS.len() - 1] as usize + 64;
+    let expected = encrypted(length);
+    let mut keystream = TableKeystream::new(NAME);
+
+    // Forwards, then backwards through the cached checkpoints.
+    for offset in OFFSETS.into_iter().chain(OFFSETS.into_iter().rev()) {
+        for size in [1, 3, 7, 61] {
+            let range = offset as usize..offset as usize + size;
+
+            let mut data = plaintext(length)[range.clone()].to_vec();
+            keystream.apply_at(offset, &mut data);
+            assert_eq!(data, expected[range.clone()], "offset {offset}, size {size}");
+            assert_eq!(keystream.position(), range.end as u64);
+
+            let mut data = plaintext(length)[range.clone()].to_vec();
+            table::xor_at(NAME, offset, &mut data);
+            assert_eq!(data, expected[range], "offset {offset}, size {size}");
+        }
+    }
+}
+
+#[test]
+fn reader_seek_matches_table_xor() {
+    let length = OFFSETS[OFFSETS.len() - 1] as usize + 64;
+    let expected = plaintext(length);
+    let mut reader = XorReader::new(Cursor::new(encrypted(length)), TableKeystream::new(NAME));
+
+    for offset in OFFSETS.into_iter().rev() {
+        assert_eq!(reader.seek(SeekFrom::Start(offset)).unwrap(), offset);
+
+        let mut data = [0u8; 13];
+        reader.read_exact(&mut data).unwrap();
+        assert_eq!(data, expected[offset as usize..offset as usize + 13], "offset {offset}");
+        assert_eq!(reader.position(), offset + 13);
+    }
+
+    reader.seek(SeekFrom::Current(-6)).unwrap();
+    let mut data = [0u8; 6];
+    reader.read_exact(&mut data).unwrap();
+    assert_eq!(data, expected[7..13]);
+}
+
+/// Accepts at most three bytes per call and fails once on call `fail_at`.
+struct Flaky {
+    output: Vec<u8>,
+    calls: usize,
+    fail_at: usize
+}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.calls += 1;
+        if self.calls == self.fail_at {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+
+        let count = buf.len().min(3);
+        self.output.extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+#[test]
+fn reader_applies_keystreams() {
+    let mut reader = XorReader::new(Cursor::new(encrypted(1000)), TableKeystream::new(NAME));
+    let mut output = Vec::new();
+    let mut chunk = [0u8; 7];
+    loop {
+        match reader.read(&mut chunk).unwrap() {
+            0 => break,
+            n => output.extend_from_slice(&chunk[..n])
+        }
+    }
+    assert_eq!(output, plaintext(1000));
+
+    let mut reader =
+        XorReader::new(&[0x10, 0x20, 0x30, 0x40, 0x50][..], ByteKey::with_range(0xFF, 1, 3));
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+    assert_eq!(output, [0x10, 0xDF, 0xCF, 0xBF, 0x50]);
+
+    let mut reader = XorReader::new(&[1, 2, 3, 4, 5][..], CyclicKey::new(&[1, 2]));
+    let mut output = [0u8; 5];
+    reader.read_exact(&mut output[..3]).unwrap();
+    reader.read_exact(&mut output[3..]).unwrap();
+    assert_eq!(output, [0, 0, 2, 6, 4]);
+}
+
+#[test]
+fn writer_applies_keystream() {
+    let mut writer = XorWriter::new(Vec::new(), TableKeystream::new(NAME));
+    for chunk in plaintext(1000).chunks(13) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.flush().unwrap();
+    assert_eq!(writer.position(), 1000);
+    assert_eq!(writer.into_inner(), encrypted(1000));
+}
+
+#[test]
+fn writer_retries_pending_bytes() {
+    let inner = Flaky {
+        output: Vec::new(),
+        calls: 0,
+        fail_at: 2
+    };
+    let mut writer = XorWriter::new(inner, TableKeystream::new(NAME));
+    let data = plaintext(20);
+
+    // Only what the inner writer took is reported, the rest stays pending.
+    assert_eq!(writer.write(&data[..11]).unwrap(), 3);
+    assert_eq!(writer.write(&data[3..11]).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(writer.position(), 3);
+    assert_eq!(writer.write(&data[5..]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    writer.write_all(&data[3..]).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.position(), 20);
+    assert_eq!(writer.get_ref().output, encrypted(20));
+}
+
+#[tokio::test]
+async fn async_adapters_round_trip() {
+    let mut writer = XorWriter::new(Vec::new(), TableKeystream::new(NAME));
+    for chunk in plaintext(1000).chunks(29) {
+        tokio::io::AsyncWriteExt::write_all(&mut writer, chunk).await.unwrap();
+    }
+    tokio::io::AsyncWriteExt::shutdown(&mut writer).await.unwrap();
+    let encrypted_output = writer.into_inner();
+    assert_eq!(encrypted_output, encrypted(1000));
+
+    let mut reader = XorReader::new(&encrypted_output[..], TableKeystream::new(NAME));
+    let mut output = Vec::new();
+    tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut output).await.unwrap();
+    assert_eq!(output, plaintext(1000));
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::io::{Cursor, Read, Write};
+
+use bacy::error::TableBundleError;
+use bacy::table::bundle::*;
+use common::TempDir;
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{AesMode, CompressionMethod, ZipArchive, ZipWriter};
+
+mod common;
+
+const FILE_NAME: &str = "Excel.zip";
+
+fn open(bytes: Vec<u8>) -> TableBundle<Cursor<Vec<u8>>> {
+    TableBundle::from_reader(Cursor::new(bytes), FILE_NAME).unwrap()
+}
+
+/// Two encrypted tables mixed with a plain text file and a directory.
+fn mixed_archive() -> Vec<u8> {
+    let password = bundle_password(FILE_NAME);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("readme.txt", stored).unwrap();
+    zip.write_all(b"not a table").unwrap();
+    zip.start_file(
+        "A.bytes",
+        stored
+            .with_aes_encryption(AesMode::Aes128, &password)
+            .compression_method(CompressionMethod::Deflated)
+    )
+    .unwrap();
+    zip.write_all(b"first").unwrap();
+    zip.add_directory("nested/", stored).unwrap();
+    zip.start_file("B.bytes", stored.with_aes_encryption(AesMode::Aes128, &password)).unwrap();
+    zip.write_all(b"second").unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn opens_reads_and_extracts() {
+    let dir = TempDir::new("table-bundle");
+    let path = dir.join(FILE_NAME);
+    fs::write(&path, mixed_archive()).unwrap();
+
+    let mut bundle = TableBundle::open(&path).unwrap();
+    assert_eq!(bundle.password(), bundle_password("excel.zip"));
+    assert_eq!(bundle.names(), ["A.bytes", "B.bytes"]);
+    assert_eq!(bundle.len(), 2);
+    assert!(!bundle.is_empty());
+    assert_eq!(bundle.read("B.bytes").unwrap(), b"second");
+    assert!(matches!(bundle.read("C.bytes"), Err(TableBundleError::EntryNotFound(_))));
+
+    let entries: Vec<_> = bundle.entries().map(|entry| entry.unwrap().name).collect();
+    assert_eq!(entries, ["A.bytes", "B.bytes"]);
+
+    let written = bundle.extract(dir.join("out")).unwrap();
+    assert_eq!(written, [dir.join("out/A.bytes"), dir.join("out/B.bytes")]);
+    assert_eq!(fs::read(dir.join("out/A.bytes")).unwrap(), b"first");
+    assert!(!dir.join("out/readme.txt").exists());
+
+    assert!(matches!(TableBundle::open(dir.join("missing.zip")), Err(TableBundleError::Io(_))));
+}
+
+#[test]
+fn rejects_wrong_password() {
+    let mut bundle = TableBundle::from_reader(Cursor::new(mixed_archive()), "Other.zip").unwrap();
+    assert!(matches!(
+        bundle.read("A.bytes"),
+        Err(TableBundleError::Zip(ZipError::InvalidPassword))
+    ));
+    assert!(bundle.entries().next().unwrap().is_err());
+
+    let empty = ZipWriter::new(Cursor::new(Vec::new())).finish().unwrap().into_inner();
+    let bundle = TableBundle::from_reader(Cursor::new(empty), FILE_NAME).unwrap();
+    assert!(bundle.is_empty());
+}
+
+#[test]
+fn writer_round_trips_through_reader() {
+    let mut writer = TableBundleWriter::new(FILE_NAME);
+    writer.insert("CharacterExcelTable.bytes", b"character".to_vec());
+    writer.insert("ItemExcelTable.bytes", b"items".to_vec());
+    writer.insert("CharacterExcelTable.bytes", b"replaced".to_vec());
+
+    let mut bundle = open(writer.to_bytes().unwrap());
+    assert_eq!(bundle.password(), writer.password());
+    assert_eq!(bundle.names(), ["CharacterExcelTable.bytes", "ItemExcelTable.bytes"]);
+    assert_eq!(bundle.read("CharacterExcelTable.bytes").unwrap(), b"replaced");
+
+    let layout = bundle.layout().unwrap();
+    assert!(layout.iter().all(|(_, layout)| layout.encryption == BundleEncryption::ZipCrypto));
+
+    let mut aes = TableBundleWriter::new(FILE_NAME).with_layout(EntryLayout {
+        encryption: BundleEncryption::Aes(AesMode::Aes256),
+        ..EntryLayout::default()
+    });
+    aes.insert("A.bytes", b"aes".to_vec());
+    let mut bundle = open(aes.to_bytes().unwrap());
+    assert_eq!(bundle.read("A.bytes").unwrap(), b"aes");
+    assert_eq!(bundle.layout().unwrap()[0].1.encryption, BundleEncryption::Aes(AesMode::Aes256));
+}
+
+#[test]
+fn template_keeps_other_entries_in_order() {
+    let template = mixed_archive();
+
+    let mut writer =
+        TableBundleWriter::from_template(FILE_NAME, &mut open(template.clone())).unwrap();
+    writer.insert("B.bytes", b"patched".to_vec());
+    writer.insert("C.bytes", b"new".to_vec());
+
+    let mut bundle = open(writer.to_bytes().unwrap());
+    let layout = bundle.layout().unwrap();
+    let names: Vec<_> = layout.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["readme.txt", "A.bytes", "nested/", "B.bytes", "C.bytes"]);
+    assert_eq!(layout[0].1.compression, CompressionMethod::Stored);
+    assert_eq!(layout[1].1.compression, CompressionMethod::Deflated);
+    assert_eq!(layout[4].1.encryption, BundleEncryption::Aes(AesMode::Aes128));
+
+    assert_eq!(bundle.read("A.bytes").unwrap(), b"first");
+    assert_eq!(bundle.read("B.bytes").unwrap(), b"patched");
+    assert_eq!(bundle.read("C.bytes").unwrap(), b"new");
+
+    let mut original = ZipArchive::new(Cursor::new(template)).unwrap();
+    let mut copied = ZipArchive::new(Cursor::new(writer.to_bytes().unwrap())).unwrap();
+    let mut text = String::new();
+    copied.by_name("readme.txt").unwrap().read_to_string(&mut text).unwrap();
+    assert_eq!(text, "not a table");
+    assert_eq!(
+        original.by_name("readme.txt").unwrap().crc32(),
+        copied.by_name("readme.txt").unwrap().crc32()
+    );
+}
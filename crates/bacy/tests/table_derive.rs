@@ -0,0 +1,117 @@
+#![cfg(feature = "derive")]
+
+use bacy::crypto::table::*;
+use bacy::error::TableEncryptionError;
+
+#[derive(Clone, Copy, Debug, PartialEq, TableDecrypt, TableEncrypt)]
+#[repr(transparent)]
+#[table(enum)]
+struct Rarity(i32);
+
+impl flatbuffers::EndianScalar for Rarity {
+    type Scalar = i32;
+
+    fn to_little_endian(self) -> i32 { self.0.to_le() }
+
+    fn from_little_endian(v: i32) -> Self { Rarity(i32::from_le(v)) }
+}
+
+#[derive(Clone, Debug, PartialEq, TableDecrypt, TableEncrypt)]
+struct RewardT {
+    amount: i64,
+    weight: f32
+}
+
+#[derive(Clone, Debug, PartialEq, TableDecrypt, TableEncrypt)]
+struct CharacterExcelT {
+    id: i64,
+    name: String,
+    rarity: Rarity,
+    tags: Vec<String>,
+    reward: Option<Box<RewardT>>,
+    missing: Option<Box<RewardT>>,
+    #[table(skip)]
+    cached: u32
+}
+
+#[derive(Clone, Debug, PartialEq, TableDecrypt, TableEncrypt)]
+#[table(key = "ItemExcel")]
+struct ItemRow {
+    id: i32
+}
+
+fn character() -> CharacterExcelT {
+    CharacterExcelT {
+        id: 10005,
+        name: "Hoshino".to_string(),
+        rarity: Rarity(3),
+        tags: vec!["Tank".to_string(), String::new()],
+        reward: Some(Box::new(RewardT {
+            amount: 1200,
+            weight: 0.25
+        })),
+        missing: None,
+        cached: 77
+    }
+}
+
+#[test]
+fn derives_key_names() {
+    assert_eq!(CharacterExcelT::KEY_NAME, "CharacterExcel");
+    assert_eq!(RewardT::KEY_NAME, "Reward");
+    assert_eq!(ItemRow::KEY_NAME, "ItemExcel");
+}
+
+#[test]
+fn encrypts_named_fields() {
+    let key = create_key("CharacterExcel");
+    let original = character();
+    let mut record = original.clone();
+    record.encrypt_record().unwrap();
+
+    assert_eq!(record.id, encrypt_i64(original.id, &key));
+    assert_eq!(record.name, encrypt_string(&original.name, &key));
+    assert_eq!(record.rarity, encrypt_enum(original.rarity, &key).unwrap());
+    assert_eq!(record.tags[0], encrypt_string("Tank", &key));
+    assert_eq!(record.tags[1], "");
+    assert_eq!(record.missing, None);
+    assert_eq!(record.cached, 77);
+
+    let reward = record.reward.as_deref().unwrap();
+    assert_eq!(reward.amount, encrypt_i64(1200, &key));
+    assert_eq!(reward.weight, encrypt_f32(0.25, &key).unwrap());
+
+    record.decrypt_record().unwrap();
+    assert_eq!(record, original);
+}
+
+#[test]
+fn uses_key_override() {
+    let mut row = ItemRow { id: 42 };
+    row.encrypt_record().unwrap();
+    assert_eq!(row.id, encrypt_i32(42, &create_key("ItemExcel")));
+
+    row.decrypt_record().unwrap();
+    assert_eq!(row.id, 42);
+}
+
+#[test]
+fn propagates_errors() {
+    let key = create_key("CharacterExcel");
+
+    // The one non-zero value that encrypts to zero.
+    let mut rarity = Rarity(encrypt_i32(0, &key));
+    assert!(matches!(rarity.encrypt(&key), Err(TableEncryptionError::EnumCollision(_))));
+
+    let mut record = character();
+    record.rarity = rarity;
+    assert!(matches!(record.encrypt_record(), Err(TableEncryptionError::EnumCollision(_))));
+
+    let mut record = character();
+    record.reward.as_mut().unwrap().weight = f32::MAX;
+    assert!(matches!(record.encrypt_record(), Err(TableEncryptionError::Overflow)));
+
+    let mut record = character();
+    record.name = "not base64!".to_string();
+    assert!(matches!(record.decrypt_record(), Err(TableEncryptionError::Base64Decode(_))));
+}
@@ -0,0 +1,115 @@
+use bacy::crypto::table::*;
+use bacy::error::TableEncryptionError;
+use proptest::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(transparent)]
+struct Kind(i32);
+
+impl flatbuffers::EndianScalar for Kind {
+    type Scalar = i32;
+
+    fn to_little_endian(self) -> i32 { self.0.to_le() }
+
+    fn from_little_endian(v: i32) -> Self { Kind(i32::from_le(v)) }
+}
+
+fn key() -> impl Strategy<Value = [u8; 8]> { "[A-Za-z]{1,24}".prop_map(|name| create_key(&name)) }
+
+proptest! {
+    #[test]
+    fn i32_round_trip(value: i32, key in key()) {
+        prop_assert_eq!(decrypt_i32(encrypt_i32(value, &key), &key), value);
+    }
+
+    #[test]
+    fn i64_round_trip(value: i64, key in key()) {
+        prop_assert_eq!(decrypt_i64(encrypt_i64(value, &key), &key), value);
+    }
+
+    #[test]
+    fn u32_round_trip(value: u32, key in key()) {
+        prop_assert_eq!(decrypt_u32(encrypt_u32(value, &key), &key), value);
+    }
+
+    #[test]
+    fn u64_round_trip(value: u64, key in key()) {
+        prop_assert_eq!(decrypt_u64(encrypt_u64(value, &key), &key), value);
+    }
+
+    #[test]
+    fn enum_round_trip(value: i32, key in key()) {
+        match encrypt_enum(Kind(value), &key) {
+            Ok(encrypted) => prop_assert_eq!(decrypt_enum(encrypted, &key), Kind(value)),
+            Err(err) => prop_assert!(matches!(err, TableEncryptionError::EnumCollision(v) if v == value))
+        }
+    }
+
+    // Scaled values times the largest multiplier (9) stay within f32's mantissa.
+    #[test]
+    fn f32_round_trip(scaled in -1_800_000i32..1_800_000, key in key()) {
+        let value = scaled as f32 / 10000.0;
+        prop_assert_eq!(decrypt_f32(encrypt_f32(value, &key).unwrap(), &key), value);
+    }
+
+    #[test]
+    fn f64_round_trip(scaled in -1_000_000_000_000i64..1_000_000_000_000, key in key()) {
+        let value = scaled as f64 / 1000000.0;
+        prop_assert_eq!(decrypt_f64(encrypt_f64(value, &key).unwrap(), &key), value);
+    }
+
+    #[test]
+    fn string_round_trip(value in "\\PC*", key in key()) {
+        prop_assert_eq!(decrypt_string(&encrypt_string(&value, &key), &key).unwrap(), value);
+    }
+
+    #[test]
+    fn trait_round_trip(mut values: Vec<i64>, text in "\\PC*", key in key()) {
+        let (original, original_text) = (values.clone(), text.clone());
+        let mut text = Some(text);
+
+        values.encrypt(&key).unwrap();
+        text.encrypt(&key).unwrap();
+        values.decrypt(&key).unwrap();
+        text.decrypt(&key).unwrap();
+
+        prop_assert_eq!(values, original);
+        prop_assert_eq!(text, Some(original_text));
+    }
+}
+
+#[test]
+fn f32_overflow() {
+    let key = create_key("Overflow");
+    assert!(matches!(encrypt_f32(1.0e9, &key), Err(TableEncryptionError::Overflow)));
+    assert!(matches!(encrypt_f32(f32::NAN, &key), Err(TableEncryptionError::Overflow)));
+}
+
+#[test]
+fn f64_overflow() {
+    let key = create_key("Overflow");
+    assert!(encrypt_f64(1.0e10, &key).is_ok());
+    assert!(matches!(encrypt_f64(1.0e13, &key), Err(TableEncryptionError::Overflow)));
+    assert!(matches!(encrypt_f64(1.0e18, &key), Err(TableEncryptionError::Overflow)));
+    assert!(matches!(encrypt_f64(f64::INFINITY, &key), Err(TableEncryptionError::Overflow)));
+}
+
+#[test]
+fn f32_rounds_large_values() {
+    // Past 2^24 the encrypted integer is rounded to the nearest f32, like the game
+    // does.
+    let key = create_key("Overflow");
+    let decrypted = decrypt_f32(encrypt_f32(1000.0, &key).unwrap(), &key);
+    assert!((decrypted - 1000.0).abs() < 0.01, "{decrypted}");
+}
+
+#[test]
+fn enum_collision() {
+    let key = create_key("Kind");
+    let colliding = i32::from_le_bytes(key[..4].try_into().unwrap());
+    assert!(matches!(
+        encrypt_enum(Kind(colliding), &key),
+        Err(TableEncryptionError::EnumCollision(v)) if v == colliding
+    ));
+    assert_eq!(encrypt_enum(Kind(0), &key).unwrap(), Kind(0));
+}
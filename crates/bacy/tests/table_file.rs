@@ -0,0 +1,94 @@
+use std::fs;
+
+use bacy::crypto::table;
+use bacy::error::TableFileError;
+use bacy::table::file::*;
+use common::TempDir;
+use flatbuffers::FlatBufferBuilder;
+
+mod common;
+
+const FILE_NAME: &str = "CharacterExcelTable.bytes";
+
+/// A table with an `i64` id and a string name, optionally size-prefixed.
+fn flatbuffer(size_prefixed: bool) -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+    let name = builder.create_string("Hoshino");
+    let start = builder.start_table();
+    builder.push_slot_always(4, 10005i64);
+    builder.push_slot_always(6, name);
+    let root = builder.end_table(start);
+
+    match size_prefixed {
+        true => builder.finish_size_prefixed(root, None),
+        false => builder.finish(root, None)
+    }
+    builder.finished_data().to_vec()
+}
+
+fn encrypted(data: &[u8], key_name: &str) -> Vec<u8> {
+    let mut data = data.to_vec();
+    table::xor(key_name, &mut data);
+    data
+}
+
+#[test]
+fn infers_key_names() {
+    assert_eq!(table_key_name(FILE_NAME).as_deref(), Some("characterexceltable"));
+    assert_eq!(table_key_name("dir/ItemExcel").as_deref(), Some("itemexcel"));
+    assert_eq!(table_key_name(""), None);
+}
+
+#[test]
+fn recognises_flatbuffers() {
+    for size_prefixed in [false, true] {
+        let data = flatbuffer(size_prefixed);
+        assert!(is_flatbuffer(&data), "size prefixed: {size_prefixed}");
+
+        // Misaligned root offset.
+        let mut corrupted = data.clone();
+        corrupted[4 * size_prefixed as usize] ^= 2;
+        assert!(!is_flatbuffer(&corrupted), "size prefixed: {size_prefixed}");
+    }
+
+    assert!(!is_flatbuffer(&[]));
+    assert!(!is_flatbuffer(&[0; 11]));
+    assert!(!is_flatbuffer(&[0xFF; 64]));
+
+    // A size prefix that does not match the length is not stripped.
+    let mut data = flatbuffer(true);
+    data[0] ^= 1;
+    assert!(!is_flatbuffer(&data));
+}
+
+#[test]
+fn decrypts_bytes() {
+    for size_prefixed in [false, true] {
+        let plain = flatbuffer(size_prefixed);
+        let data = encrypted(&plain, "characterexceltable");
+        assert_eq!(decrypt_table_bytes(FILE_NAME, data).unwrap(), plain);
+    }
+
+    let data = encrypted(&flatbuffer(false), "characterexceltable");
+    assert!(matches!(
+        decrypt_table_bytes("ItemExcelTable.bytes", data.clone()),
+        Err(TableFileError::InvalidFlatBuffer(name)) if name == "itemexceltable"
+    ));
+    assert!(matches!(decrypt_table_bytes("", data), Err(TableFileError::InvalidPath)));
+}
+
+#[test]
+fn decrypts_files() {
+    let dir = TempDir::new("table-file");
+
+    let plain = flatbuffer(true);
+    fs::write(dir.join(FILE_NAME), encrypted(&plain, "characterexceltable")).unwrap();
+    assert_eq!(decrypt_table_file(dir.join(FILE_NAME)).unwrap(), plain);
+
+    fs::copy(dir.join(FILE_NAME), dir.join("Other.bytes")).unwrap();
+    assert!(matches!(
+        decrypt_table_file(dir.join("Other.bytes")),
+        Err(TableFileError::InvalidFlatBuffer(_))
+    ));
+    assert!(matches!(decrypt_table_file(dir.join("Missing.bytes")), Err(TableFileError::Io(_))));
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bacy::catalog::verify::*;
+use common::TempDir;
+
+mod common;
+
+fn expected(name: &str, data: &[u8]) -> ExpectedFile {
+    ExpectedFile {
+        name: name.to_string(),
+        path: PathBuf::from("data").join(name),
+        size: data.len() as i64,
+        crc: crc32fast::hash(data) as i64
+    }
+}
+
+#[test]
+fn reports_missing_corrupted_and_unexpected() {
+    let root = TempDir::new("verify");
+    fs::create_dir(root.join("data")).unwrap();
+
+    fs::write(root.join("data/good"), b"good").unwrap();
+    fs::write(root.join("data/flipped"), b"flopped").unwrap();
+    fs::write(root.join("data/short"), b"sho").unwrap();
+    fs::write(root.join("data/extra"), b"extra").unwrap();
+    fs::create_dir_all(root.join("data/nested")).unwrap();
+    fs::write(root.join("data/nested/skipped"), b"skipped").unwrap();
+    fs::create_dir_all(root.join("other")).unwrap();
+    fs::write(root.join("other/unrelated"), b"unrelated").unwrap();
+
+    let verifier = Verifier::new(&root)
+        .with_threads(3)
+        .with_file(expected("good", b"good"))
+        .with_file(expected("flipped", b"flipped"))
+        .with_file(expected("short", b"short"))
+        .with_file(expected("gone", b"gone"))
+        .with_file(expected("good/child", b"child"));
+
+    let mut calls = Vec::new();
+    let report = verifier.verify(|p| calls.push((p.checked, p.total))).unwrap();
+
+    assert_eq!(calls, [(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]);
+    assert_eq!(report.verified, 1);
+    assert_eq!(report.missing, [expected("gone", b"gone")]);
+    assert_eq!(report.unexpected, [Path::new("data/extra")]);
+
+    let corrupted: Vec<_> =
+        report.corrupted.iter().map(|c| (c.file.name.as_str(), c.actual_crc)).collect();
+    assert_eq!(corrupted, [("flipped", Some(crc32fast::hash(b"flopped"))), ("short", None)]);
+
+    // `data/good` is a file, so its child cannot be read.
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].file, expected("good/child", b"child"));
+    assert!(!report.is_ok());
+}
@@ -0,0 +1,75 @@
+use bacy::crypto::{table, xor};
+use bacy::hash::xxhash;
+use bacy::math::mersenne::{BLOCK_BYTES, MersenneBlocks, MersenneTwister};
+
+// Around the 256-byte block path and the 1 MiB rayon threshold.
+const LENGTHS: [usize; 10] = [0, 1, 7, 8, 255, 256, 257, 4099, (1 << 20) - 3, (1 << 20) + 13];
+
+fn data(length: usize) -> Vec<u8> { (0..length).map(|i| (i * 31 + 7) as u8).collect() }
+
+fn scalar_xor(data: &mut [u8], key: &[u8]) {
+    data.iter_mut().zip(key.iter().cycle()).for_each(|(d, k)| *d ^= k);
+}
+
+fn scalar_table_xor(name: &str, data: &mut [u8]) {
+    let mut mt = MersenneTwister::new(xxhash::calculate_hash_str(name));
+    for chunk in data.chunks_mut(4) {
+        let key = mt.next_u32().to_le_bytes();
+        chunk.iter_mut().zip(key).for_each(|(d, k)| *d ^= k);
+    }
+}
+
+#[test]
+fn xor_inplace_matches_scalar_loop() {
+    for length in LENGTHS {
+        let original = data(length);
+        for key_length in 1..=17 {
+            let key: Vec<u8> = (0..key_length).map(|i| 0xA0 ^ (i as u8 * 13)).collect();
+
+            let mut expected = original.clone();
+            scalar_xor(&mut expected, &key);
+            let mut actual = original.clone();
+            xor::xor_inplace(&mut actual, &key);
+
+            assert!(actual == expected, "length {length}, key length {key_length}");
+        }
+    }
+
+    let mut unchanged = data(300);
+    xor::xor_inplace(&mut unchanged, &[]);
+    assert_eq!(unchanged, data(300));
+}
+
+#[test]
+fn table_xor_matches_scalar_loop() {
+    for length in LENGTHS.into_iter().chain([BLOCK_BYTES - 1, BLOCK_BYTES, BLOCK_BYTES * 2 + 1]) {
+        let mut expected = data(length);
+        scalar_table_xor("CharacterExcelTable", &mut expected);
+        let mut actual = data(length);
+        table::xor("CharacterExcelTable", &mut actual);
+
+        assert!(actual == expected, "length {length}");
+    }
+}
+
+#[test]
+fn blocks_match_twister() {
+    let mut mt = MersenneTwister::new(5489);
+    let mut blocks = MersenneBlocks::new(5489);
+    let mut block = [0u8; BLOCK_BYTES];
+
+    for _ in 0..3 {
+        blocks.next_block(&mut block);
+        for word in block.as_chunks::<4>().0 {
+            assert_eq!(u32::from_le_bytes(*word), mt.next_u32());
+        }
+    }
+
+    let mut twister = blocks.to_twister();
+    assert_eq!(twister.next_u32(), mt.next_u32());
+
+    let mut skipped = MersenneBlocks::new(5489);
+    skipped.skip_blocks(4);
+    mt.discard(623);
+    assert_eq!(skipped.to_twister().next_u32(), mt.next_u32());
+}